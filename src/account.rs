@@ -0,0 +1,150 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::input::InputError;
+
+/// Environment variable holding the session token of the default account.
+const SESSION_VAR: &str = "AOC_SESSION";
+
+/// An adventofcode.com account to make requests as.
+///
+/// Every account gets different inputs, so anything cached on disk is namespaced by account. The
+/// default (unnamed) account keeps the original un-namespaced layout.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Account {
+    name: Option<String>,
+    session: Option<String>,
+}
+
+impl Account {
+    /// A named account, e.g. `work` for `--account work`.
+    ///
+    /// The name is used as a directory name and, uppercased, in an environment variable name, so it
+    /// can only contain lowercase ASCII letters, digits and underscores. That way it can't escape
+    /// the directories it is used in, and no two accounts share a variable.
+    pub fn named(name: impl Into<String>) -> Result<Self, InvalidName> {
+        let name = name.into();
+        if name.is_empty() || !name.chars().all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '_') {
+            return Err(InvalidName(name));
+        }
+        Ok(Self { name: Some(name), session: None })
+    }
+
+    /// Use the given session token instead of looking one up.
+    pub fn with_session(self, token: impl Into<String>) -> Self {
+        Self { session: Some(token.into()), ..self }
+    }
+
+    /// Returns the account's name, `default` for the unnamed account.
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or("default")
+    }
+
+    /// Namespace a directory by this account.
+    pub(crate) fn scope(&self, root: impl AsRef<Path>) -> PathBuf {
+        match &self.name {
+            Some(name) => root.as_ref().join(name),
+            None => root.as_ref().to_owned(),
+        }
+    }
+
+    /// Resolve the session token for this account.
+    ///
+    /// The token is looked up from, in order:
+    /// 1. The `AOC_SESSION` environment variable (`AOC_SESSION_<NAME>` for named accounts).
+    /// 2. `$XDG_CONFIG_HOME/aoc/session` (`session.<name>` for named accounts), where
+    ///    `XDG_CONFIG_HOME` defaults to `~/.config`.
+    /// 3. `session.txt` (`session.<name>.txt`) in the current directory.
+    ///
+    /// A token given with [`Account::with_session`] takes precedence over all of these.
+    pub fn session_token(&self) -> Result<String, InputError> {
+        if let Some(session) = &self.session {
+            return Ok(session.clone());
+        }
+        self.resolve_token(|var| std::env::var(var).ok(), config_dir().as_deref())
+    }
+
+    fn resolve_token(
+        &self,
+        env: impl Fn(&str) -> Option<String>,
+        config_dir: Option<&Path>,
+    ) -> Result<String, InputError> {
+        let (var, config_file, legacy_file) = match &self.name {
+            Some(name) => (
+                format!("{SESSION_VAR}_{}", name.to_ascii_uppercase()),
+                format!("session.{name}"),
+                format!("session.{name}.txt"),
+            ),
+            None => (SESSION_VAR.to_owned(), "session".to_owned(), "session.txt".to_owned()),
+        };
+
+        let candidates = [config_dir.map(|dir| dir.join("aoc").join(config_file)), Some(PathBuf::from(legacy_file))];
+        let token = env(&var)
+            .filter(|token| !token.trim().is_empty())
+            .or_else(|| candidates.into_iter().flatten().find_map(|path| std::fs::read_to_string(path).ok()));
+
+        match token.map(|token| token.trim().to_owned()) {
+            Some(token) if !token.is_empty() => Ok(token),
+            _ => Err(InputError::MissingSession { account: self.name().to_owned(), var }),
+        }
+    }
+}
+
+/// Returned when an account name has characters other than lowercase ASCII letters, digits and
+/// underscores.
+#[derive(Debug)]
+pub struct InvalidName(pub String);
+
+impl fmt::Display for InvalidName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid account name {:?}, names can only contain a-z, 0-9 and _", self.0)
+    }
+}
+
+impl std::error::Error for InvalidName {}
+
+/// Returns `$XDG_CONFIG_HOME`, falling back to `~/.config`.
+fn config_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+    use crate::mock::TempDir;
+
+    #[test]
+    fn resolves_token_in_order() {
+        let config_dir = TempDir::new("account");
+        fs::create_dir_all(config_dir.path().join("aoc")).unwrap();
+        fs::write(config_dir.path().join("aoc").join("session.work"), "from-config\n").unwrap();
+
+        let work = Account::named("work").unwrap();
+        let env = |var: &str| (var == "AOC_SESSION_WORK").then(|| "from-env".to_owned());
+        assert_eq!(work.resolve_token(env, Some(config_dir.path())).unwrap(), "from-env");
+        assert_eq!(work.resolve_token(|_| None, Some(config_dir.path())).unwrap(), "from-config");
+        assert!(matches!(
+            Account::named("missing").unwrap().resolve_token(|_| None, Some(config_dir.path())),
+            Err(InputError::MissingSession { .. })
+        ));
+    }
+
+    #[test]
+    fn scopes_by_name() {
+        assert_eq!(Account::default().scope(".cache"), PathBuf::from(".cache"));
+        assert_eq!(Account::named("work").unwrap().scope(".cache"), PathBuf::from(".cache/work"));
+    }
+
+    #[test]
+    fn rejects_names_that_escape_the_root_or_share_a_variable() {
+        for name in ["", ".", "..", "../x", "/tmp", "a/b", "a\\b", "x..", "work-1", "Work", "work.2024"] {
+            assert!(Account::named(name).is_err(), "{name:?} was accepted");
+        }
+        assert!(Account::named("work_2024").is_ok());
+    }
+}
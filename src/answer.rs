@@ -0,0 +1,79 @@
+use std::fmt;
+
+/// The answer produced by one part of a challenge.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Answer {
+    Integer(u64),
+    SignedInteger(i64),
+    String(String),
+    /// An answer accompanied by something the solution drew to get there (e.g. the picture hidden
+    /// in the input). The artefact is for humans, only the inner answer is ever submitted.
+    Rendered {
+        answer: Box<Answer>,
+        artefact: String,
+    },
+}
+
+impl Answer {
+    /// Attach a rendered artefact to an answer.
+    pub fn rendered(answer: impl Into<Answer>, artefact: impl Into<String>) -> Self {
+        Self::Rendered { answer: Box::new(answer.into()), artefact: artefact.into() }
+    }
+
+    /// Returns the answer without any rendered artefact.
+    pub fn value(&self) -> &Self {
+        match self {
+            Self::Rendered { answer, .. } => answer.value(),
+            other => other,
+        }
+    }
+
+    /// Returns the rendered artefact, if there is one.
+    pub fn artefact(&self) -> Option<&str> {
+        match self {
+            Self::Rendered { artefact, .. } => Some(artefact),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer(value) => write!(f, "{value}"),
+            Self::SignedInteger(value) => write!(f, "{value}"),
+            Self::String(value) => write!(f, "{value}"),
+            Self::Rendered { answer, .. } => write!(f, "{answer}"),
+        }
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Self::Integer(value.into())
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Self::Integer(value as u64)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Self::SignedInteger(value.into())
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
@@ -0,0 +1,159 @@
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use std::{fs, io};
+
+use serde::Serialize;
+
+use crate::date::ChallengeDate;
+use crate::solutions::{Part, Solution};
+use crate::Input;
+
+/// Summary statistics over the timed iterations of a benchmark, in nanoseconds.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Stats {
+    pub min: u64,
+    pub median: u64,
+    pub mean: u64,
+    /// Sample standard deviation, zero if there was only one iteration.
+    pub stddev: u64,
+}
+
+impl Stats {
+    /// Summarise the given samples, returning `None` if there are none.
+    pub fn of(samples: &[Duration]) -> Option<Self> {
+        let mut nanos: Vec<f64> = samples.iter().map(|sample| sample.as_nanos() as f64).collect();
+        if nanos.is_empty() {
+            return None;
+        }
+        nanos.sort_by(f64::total_cmp);
+        let len = nanos.len();
+        let median = if len.is_multiple_of(2) { (nanos[len / 2 - 1] + nanos[len / 2]) / 2.0 } else { nanos[len / 2] };
+        let mean = nanos.iter().sum::<f64>() / len as f64;
+        let variance = match len {
+            1 => 0.0,
+            len => nanos.iter().map(|nanos| (nanos - mean).powi(2)).sum::<f64>() / (len - 1) as f64,
+        };
+        Some(Self { min: nanos[0] as u64, median: median as u64, mean: mean as u64, stddev: variance.sqrt() as u64 })
+    }
+}
+
+/// The results of benchmarking some parts of a day's solution.
+#[derive(Clone, Debug, Serialize)]
+pub struct Benchmark {
+    pub year: u16,
+    pub day: u8,
+    pub iterations: usize,
+    /// Only present for days that parse their input separately from solving it.
+    pub parse: Option<Stats>,
+    /// How long solving each part took, or all of them at once for days that solve them together.
+    pub solve: Vec<Solve>,
+}
+
+/// The time taken to solve one or more parts that are solved together.
+#[derive(Clone, Debug, Serialize)]
+pub struct Solve {
+    pub parts: Vec<u8>,
+    pub answers: Vec<String>,
+    pub time: Stats,
+}
+
+impl Benchmark {
+    /// Run the parts `warmup` times untimed, then `iterations` times timed, against a copy of the
+    /// input read into memory up front so reading it isn't measured.
+    pub fn run(solution: &Solution, parts: &[Part], input: &str, warmup: usize, iterations: usize) -> Self {
+        for _ in 0..warmup {
+            solution.time(parts, Input::memory(input));
+        }
+        let (mut parses, mut solves, mut last) = (Vec::new(), Vec::new(), None);
+        for _ in 0..iterations.max(1) {
+            let timing = solution.time(parts, Input::memory(input));
+            parses.extend(timing.parse);
+            solves.resize_with(timing.solve.len(), Vec::new);
+            for (samples, (_, time)) in solves.iter_mut().zip(&timing.solve) {
+                samples.push(*time);
+            }
+            last = Some(timing);
+        }
+
+        let last = last.unwrap();
+        let answer = |part: &Part| {
+            let idx = parts.iter().position(|other| other == part).unwrap();
+            last.answers[idx].value().to_string()
+        };
+        let solve = last
+            .solve
+            .iter()
+            .zip(&solves)
+            .map(|((group, _), samples)| Solve {
+                parts: group.iter().map(|&part| part as u8 + 1).collect(),
+                answers: group.iter().map(answer).collect(),
+                time: Stats::of(samples).unwrap(),
+            })
+            .collect();
+        let ChallengeDate { year, day } = solution.date;
+        Self { year, day, iterations: iterations.max(1), parse: Stats::of(&parses), solve }
+    }
+
+    /// The median time for one run of the day's parts, counting parsing and any combined solve
+    /// once.
+    pub fn median(&self) -> Duration {
+        let parse = self.parse.as_ref().map_or(0, |parse| parse.median);
+        Duration::from_nanos(parse + self.solve.iter().map(|solve| solve.time.median).sum::<u64>())
+    }
+}
+
+/// Save the benchmarks to a JSON file at the given path, along with when they were run.
+pub fn save(path: &Path, benchmarks: &[Benchmark]) -> io::Result<()> {
+    #[derive(Serialize)]
+    struct Report<'a> {
+        /// Seconds since the Unix epoch.
+        created: u64,
+        benchmarks: &'a [Benchmark],
+    }
+
+    let created = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).map_or(0, |since| since.as_secs());
+    let json = serde_json::to_string_pretty(&Report { created, benchmarks })?;
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory)?;
+    }
+    fs::write(path, json)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::solutions;
+
+    #[test]
+    fn summarises_samples() {
+        let samples = [4, 1, 3, 2].map(Duration::from_nanos);
+        assert_eq!(Stats::of(&samples), Some(Stats { min: 1, median: 2, mean: 2, stddev: 1 }));
+        assert_eq!(Stats::of(&[Duration::from_nanos(5)]), Some(Stats { min: 5, median: 5, mean: 5, stddev: 0 }));
+        assert_eq!(Stats::of(&[]), None);
+    }
+
+    #[test]
+    fn times_parsing_and_combined_solves_once() {
+        let input = "3   4\n4   3\n2   5\n1   3\n";
+        let solution = solutions::find(ChallengeDate { year: 2024, day: 1 }).unwrap();
+        let benchmark = Benchmark::run(solution, &Part::ALL, input, 1, 3);
+        assert_eq!(benchmark.iterations, 3);
+        assert!(benchmark.parse.is_some());
+        let solves: Vec<_> = benchmark.solve.iter().map(|solve| (solve.parts.clone(), solve.answers.clone())).collect();
+        assert_eq!(solves, [(vec![1], vec!["5".to_owned()]), (vec![2], vec!["10".to_owned()])]);
+
+        let input = "AAAA\nBBCD\nBBCC\nEEEC\n";
+        let solution = solutions::find(ChallengeDate { year: 2024, day: 12 }).unwrap();
+        let benchmark = Benchmark::run(solution, &Part::ALL, input, 0, 2);
+        assert!(benchmark.parse.is_none());
+        assert_eq!(benchmark.solve.len(), 1);
+        assert_eq!(benchmark.solve[0].parts, [1, 2]);
+        assert_eq!(benchmark.solve[0].answers, ["140", "80"]);
+
+        let input = "7 6 4 2 1\n1 2 7 8 9\n";
+        let solution = solutions::find(ChallengeDate { year: 2024, day: 2 }).unwrap();
+        let benchmark = Benchmark::run(solution, &[Part::Two], input, 0, 2);
+        assert!(benchmark.parse.is_none());
+        assert_eq!(benchmark.solve[0].parts, [2]);
+    }
+}
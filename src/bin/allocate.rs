@@ -14,7 +14,19 @@ struct SolutionsFile {
 
 impl SolutionsFile {
     fn insert_next_day(&mut self) -> usize {
-        let next_day = self.macro_.tokens.clone().into_iter().count() / 2 + 1;
+        // Days may be annotated (e.g. `day1: parse`), so find the last day rather than counting.
+        let next_day = self
+            .macro_
+            .tokens
+            .clone()
+            .into_iter()
+            .filter_map(|token| match token {
+                TokenTree::Ident(ident) => ident.to_string().strip_prefix("day")?.parse::<usize>().ok(),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0)
+            + 1;
         self.macro_.tokens.extend([
             TokenTree::Punct(Punct::new(',', Spacing::Alone)),
             TokenTree::Ident(Ident::new(&format!("day{}", next_day), Span::call_site())),
@@ -1,13 +1,596 @@
-use aoc::solutions;
+use std::any::Any;
+use std::io::Write;
+use std::panic::AssertUnwindSafe;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use aoc::account::Account;
+use aoc::bench::{self, Benchmark};
+use aoc::cache::{self, Cache, CacheKey};
+use aoc::date::ChallengeDate;
+use aoc::description::{self, Description, Format};
+use aoc::known::KnownAnswers;
+use aoc::leaderboard::Leaderboard;
+use aoc::ledger::Ledger;
+use aoc::solutions::{self, Part, Solution};
+use aoc::submit::Verdict;
+use aoc::{fetch, stats, submit, Input};
+
+const USAGE: &str = "Usage: cargo run -- [submit] <date> <part> [--account <name>] [--cache-dir <path>]
+       cargo run -- <date> [<part>] [--input <path|->]
+       cargo run -- <date> [<part>] --wait
+       cargo run -- (<year> | all)
+       cargo run -- record <date> <part>
+       cargo run -- verify [<year>]
+       cargo run -- bench <year> [<day> [<part>]] [--warmup <n>] [--iterations <n>] [--output <path>]
+       cargo run -- describe <date> [--markdown] [--refresh]
+       cargo run -- examples <date>
+       cargo run -- fetch (<year> [<day>...] | <date>...) [--delay <seconds>]
+       cargo run -- leaderboard <year> <id> [<day> | --times]
+       cargo run -- stats <year>
+       cargo run -- scan-cache [--remove] [--cache-dir <path>]
+       cargo run -- encrypt-cache [--cache-dir <path>]
+
+A <date> is `<year> <day>`, `2024/7`, `2024-07`, `24d7` or `today`.";
 
 fn main() {
-    let args: Vec<_> = std::env::args().collect();
-    if args.len() != 4 {
-        println!("Usage: cargo run -- <year> <day> <part>");
+    let mut args: Vec<_> = std::env::args().skip(1).collect();
+    let account = take_option(&mut args, "--account")
+        .map_or_else(Account::default, |name| Account::named(name).unwrap_or_else(exit));
+    let cache = Cache::locate(take_option(&mut args, "--cache-dir").map(Into::into))
+        .with_key(CacheKey::from_env().unwrap_or_else(exit))
+        .with_warnings(|warning| eprintln!("{warning}"));
+    let wait = take_flag(&mut args, "--wait");
+    let input = take_option(&mut args, "--input");
+    let delay = take_option(&mut args, "--delay").map(|secs| secs.parse().unwrap_or_else(|_| exit(USAGE)));
+    let warmup = take_option(&mut args, "--warmup").map(|count| count.parse().unwrap_or_else(|_| exit(USAGE)));
+    let iterations = take_option(&mut args, "--iterations").map(|count| count.parse().unwrap_or_else(|_| exit(USAGE)));
+    let output = take_option(&mut args, "--output").map(PathBuf::from);
+    match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["scan-cache", flags @ ..] => {
+            let remove = match flags {
+                [] => false,
+                ["--remove"] => true,
+                _ => exit(USAGE),
+            };
+            scan_cache(&cache, remove);
+        },
+        ["encrypt-cache"] => {
+            if cache.key().is_none() {
+                exit("set AOC_CACHE_KEY or AOC_CACHE_KEY_FILE to the passphrase to encrypt the cache with")
+            }
+            let encrypted = cache::encrypt_in_place(&cache).unwrap_or_else(exit);
+            for path in &encrypted {
+                println!("{}: encrypted", path.display());
+            }
+            println!("Encrypted {} inputs in {}", encrypted.len(), cache.root().display());
+        },
+        ["describe", args @ ..] => {
+            let (date, flags) = parse_date(args);
+            let (mut format, mut refresh) = (Format::Terminal, false);
+            for flag in flags {
+                match *flag {
+                    "--markdown" => format = Format::Markdown,
+                    "--refresh" => refresh = true,
+                    _ => exit(USAGE),
+                }
+            }
+            let description = Description::fetch(date, &account, &cache, refresh).unwrap_or_else(exit);
+            print!("{}", description.render(format));
+        },
+        ["examples", args @ ..] => {
+            let (date, []) = parse_date(args) else { exit(USAGE) };
+            let description = Description::fetch(date, &account, &cache, false).unwrap_or_else(exit);
+            let examples = description.examples();
+            if examples.is_empty() {
+                exit("No examples found in the description")
+            }
+            for example in &examples {
+                if example.answer.is_none() {
+                    eprintln!("No answer found for the part {} example", example.part);
+                }
+                for path in example.write_fixture(&description::fixture_dir(&date)).unwrap_or_else(exit) {
+                    println!("Wrote {}", path.display());
+                }
+            }
+            println!(
+                "Check the fixtures against the description, then add `examples!({}, {}, ...)` to the solution",
+                date.year, date.day
+            );
+        },
+        ["leaderboard", year, id, day @ ..] => {
+            let year = parse_year(year);
+            let id = id.trim().parse().unwrap_or_else(|_| exit(USAGE));
+            let leaderboard =
+                Leaderboard::fetch(year, id, &account, &cache).unwrap_or_else(|error| exit(format!("{error:#}")));
+            let rendered = match day {
+                ["--times"] => leaderboard.render_times(),
+                [] => leaderboard.render(leaderboard.latest_day().unwrap_or(1)),
+                [day] => leaderboard.render(parse_date(&[&year.to_string(), day]).0.day),
+                _ => exit(USAGE),
+            };
+            print!("{rendered}");
+        },
+        ["stats", year] => {
+            let year = parse_year(year);
+            print_stats(year, &account, &cache);
+        },
+        ["fetch", year, days @ ..] if year.parse::<u16>().is_ok() => {
+            let year = parse_year(year);
+            let dates: Vec<_> = match days {
+                [] => ChallengeDate::all_in(year).collect(),
+                days => days.iter().map(|day| parse_date(&[&year.to_string(), day]).0).collect(),
+            };
+            prefetch(dates, &account, &cache, Duration::from_secs_f64(delay.unwrap_or(5.0)));
+        },
+        ["fetch", dates @ ..] if !dates.is_empty() => {
+            let dates = dates.iter().map(|date| parse_date(&[date]).0).collect();
+            prefetch(dates, &account, &cache, Duration::from_secs_f64(delay.unwrap_or(5.0)));
+        },
+        ["submit", args @ ..] => {
+            let (date, [part]) = parse_date(args) else { exit(USAGE) };
+            if wait {
+                exit(USAGE)
+            }
+            let part = parse_part(part);
+            if input.is_some() {
+                exit("--input cannot be used with submit, answers must come from the account's input")
+            }
+            let answer = run(date, part, &account, &cache, None);
+            let mut ledger = Ledger::load(&date, part, &account, &cache).unwrap_or_else(exit);
+            if let Err(refusal) = ledger.check(&answer) {
+                exit(format!("Not submitting {answer}: {refusal}"))
+            }
+            let verdict =
+                submit::submit(date, part, &answer, &account).unwrap_or_else(|error| exit(format!("{error:#}")));
+            println!("Submitted {answer}: {verdict}");
+            let correct = verdict == Verdict::Correct;
+            if let Err(error) = ledger.record(&answer, verdict) {
+                eprintln!("failed to record submission: {error}");
+            }
+            if correct {
+                record(date, part, &answer, &account, &cache);
+            }
+        },
+        ["record", args @ ..] => {
+            let (date, [part]) = parse_date(args) else { exit(USAGE) };
+            let part = parse_part(part);
+            if input.is_some() {
+                exit("--input cannot be used with record, answers must come from the account's input")
+            }
+            let answer = run(date, part, &account, &cache, None);
+            record(date, part, &answer, &account, &cache);
+        },
+        ["bench", year, args @ ..] => {
+            let year = parse_year(year);
+            let (day, part) = match args {
+                [] => (None, None),
+                [day] => (Some(parse_date(&[&year.to_string(), day]).0.day), None),
+                [day, part] => (Some(parse_date(&[&year.to_string(), day]).0.day), Some(parse_part(part))),
+                _ => exit(USAGE),
+            };
+            let output = output.unwrap_or_else(|| {
+                let created = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
+                PathBuf::from(".benchmarks").join(format!("{year}-{created}.json"))
+            });
+            let (benchmarks, failures) =
+                run_bench(year, day, part, warmup.unwrap_or(3), iterations.unwrap_or(10), &account, &cache);
+            bench::save(&output, &benchmarks).unwrap_or_else(exit);
+            println!("Saved results to {}", output.display());
+            if failures > 0 {
+                exit(format!("{failures} days failed"))
+            }
+        },
+        ["verify"] => verify(None, &account, &cache),
+        ["verify", year] => verify(Some(parse_year(year)), &account, &cache),
+        [] => exit(USAGE),
+        ["all"] => run_all(None, &account, &cache),
+        [year] if year.parse::<u16>().is_ok() => run_all(Some(parse_year(year)), &account, &cache),
+        args => {
+            let (date, part) = match parse_date(args) {
+                (date, [part]) => (date, Some(parse_part(part))),
+                (date, []) if wait => (date, Some(Part::One)),
+                (date, []) => (date, None),
+                _ => exit(USAGE),
+            };
+            if wait {
+                wait_for_unlock(date);
+            }
+            match part {
+                Some(part) => _ = run(date, part, &account, &cache, input.as_deref()),
+                None => run_both(date, &account, &cache, input.as_deref()),
+            }
+        },
+    }
+}
+
+/// Remove `<name> <value>` from the arguments and return the value.
+fn take_option(args: &mut Vec<String>, name: &str) -> Option<String> {
+    let idx = args.iter().position(|arg| arg == name)?;
+    if idx + 1 >= args.len() {
+        exit(USAGE)
+    }
+    args.remove(idx);
+    Some(args.remove(idx))
+}
+
+/// Remove the flag from the arguments and return whether it was present.
+fn take_flag(args: &mut Vec<String>, name: &str) -> bool {
+    let len = args.len();
+    args.retain(|arg| arg != name);
+    args.len() != len
+}
+
+/// Parse the challenge date from the start of the arguments, returning it along with the rest of
+/// the arguments.
+///
+/// The date is either `today`, a single argument like `2024/7`, or a year and day.
+fn parse_date<'a, 'b>(args: &'a [&'b str]) -> (ChallengeDate, &'a [&'b str]) {
+    match args {
+        ["today", rest @ ..] => {
+            let today = ChallengeDate::today().unwrap_or_else(|| exit("There is no challenge today"));
+            (today, rest)
+        },
+        [date, rest @ ..] if date.parse::<u16>().is_err() => (date.parse().unwrap_or_else(exit), rest),
+        [year, day, rest @ ..] => {
+            let year = parse_year(year);
+            let day = day.trim().parse().unwrap_or_else(|_| exit(USAGE));
+            (ChallengeDate::new(year, day).unwrap_or_else(exit), rest)
+        },
+        _ => exit(USAGE),
+    }
+}
+
+/// Parse a year that AoC has run.
+fn parse_year(year: &str) -> u16 {
+    ChallengeDate::parse_year(year).unwrap_or_else(exit)
+}
+
+fn parse_part(part: &str) -> Part {
+    let part = part.trim().parse::<u8>().unwrap_or_else(|_| exit(USAGE));
+    Part::try_from(part).unwrap_or_else(exit)
+}
+
+/// Run one part of the solution for the given date and print its answer.
+///
+/// The input is read from the given path (or stdin for `-`) instead of the account's input if
+/// one is given.
+fn run(date: ChallengeDate, part: Part, account: &Account, cache: &Cache, input: Option<&str>) -> aoc::Answer {
+    let solution = solutions::find(date).unwrap_or_else(exit);
+    let answer = solution.run(part, load_input(date, account, cache, input));
+    print_answer(part, &answer);
+    answer
+}
+
+/// Run both parts of the solution for the given date from a single read of the input and print
+/// their answers.
+fn run_both(date: ChallengeDate, account: &Account, cache: &Cache, input: Option<&str>) {
+    let solution = solutions::find(date).unwrap_or_else(exit);
+    let answers = solution.run_both(load_input(date, account, cache, input));
+    for (part, answer) in Part::ALL.into_iter().zip(&answers) {
+        print_answer(part, answer);
+    }
+}
+
+/// Run both parts of every implemented solution (for the given year, if any) against the cached
+/// inputs and print a table of their answers and how long each day took.
+///
+/// Failures (including inputs that aren't cached) are shown in the table rather than stopping the
+/// run, and make the process exit with a failure status at the end.
+fn run_all(year: Option<u16>, account: &Account, cache: &Cache) {
+    let solutions: Vec<_> =
+        solutions::all().filter(|solution| year.is_none_or(|year| solution.date.year == year)).collect();
+    if solutions.is_empty() {
+        exit("No solutions have been implemented for that year")
+    }
+
+    let mut failures = 0;
+    let mut totals: Vec<(u16, Duration)> = Vec::new();
+    println!("{:>4} {:>3} {:>12}  {:<20} Part 2", "Year", "Day", "Time", "Part 1");
+    with_silenced_panics(|| {
+        for solution in solutions {
+            let date = solution.date;
+            let result = read_cached_input(date, account, cache).and_then(|input| {
+                let started = Instant::now();
+                try_run_both(solution, input).map(|answers| (answers, started.elapsed()))
+            });
+            // Only successful runs count towards the year's total.
+            let (time, answers, elapsed) = match result {
+                Ok(([part1, part2], elapsed)) => {
+                    (format!("{elapsed:.2?}"), format!("{:<20} {}", part1.value().to_string(), part2.value()), elapsed)
+                },
+                Err(error) => {
+                    failures += 1;
+                    ("-".to_owned(), format!("FAILED: {error}"), Duration::ZERO)
+                },
+            };
+            println!("{:>4} {:>3} {time:>12}  {answers}", date.year, date.day);
+            match totals.last_mut() {
+                Some((year, total)) if *year == date.year => *total += elapsed,
+                _ => totals.push((date.year, elapsed)),
+            }
+        }
+    });
+
+    for (year, total) in totals {
+        println!("{year:>4} {:>3} {:>12}  Total", "", format!("{total:.2?}"));
+    }
+    if failures > 0 {
+        exit(format!("{failures} days failed"))
+    }
+}
+
+/// Benchmark the parts of the year's solutions (or just the given day and part) on their cached
+/// inputs, printing a table of the results and the year's total median time.
+///
+/// Days whose input isn't cached or whose solution panics are reported and skipped, so this
+/// returns the benchmarks that completed along with how many days failed.
+fn run_bench(
+    year: u16,
+    day: Option<u8>,
+    part: Option<Part>,
+    warmup: usize,
+    iterations: usize,
+    account: &Account,
+    cache: &Cache,
+) -> (Vec<Benchmark>, usize) {
+    let solutions: Vec<_> = solutions::all()
+        .filter(|solution| solution.date.year == year && day.is_none_or(|day| solution.date.day == day))
+        .collect();
+    match (solutions.is_empty(), day) {
+        (true, Some(day)) => exit(solutions::NotImplemented(ChallengeDate { year, day })),
+        (true, None) => exit("No solutions have been implemented for that year"),
+        _ => {},
+    }
+
+    let parts = part.map_or(Part::ALL.to_vec(), |part| vec![part]);
+    let ns = |nanos: u64| format!("{:.2?}", Duration::from_nanos(nanos));
+    println!(
+        "{:>3} {:>4} {:>10}  {:>10} {:>10} {:>10} {:>10}",
+        "Day", "Part", "Parse", "Min", "Median", "Mean", "Stddev"
+    );
+    let (mut benchmarks, mut failures) = (Vec::new(), 0);
+    with_silenced_panics(|| {
+        for solution in solutions {
+            let date = solution.date;
+            let result = read_cached_input(date, account, cache).and_then(|input| {
+                std::panic::catch_unwind(AssertUnwindSafe(|| {
+                    Benchmark::run(solution, &parts, &input, warmup, iterations)
+                }))
+                .map_err(panic_message)
+            });
+            let benchmark = match result {
+                Ok(benchmark) => benchmark,
+                Err(error) => {
+                    failures += 1;
+                    println!("{:>3} {:>4} {:>10}  FAILED: {error}", date.day, "-", "-");
+                    continue;
+                },
+            };
+            // The parse is shared by every part, so it is only shown once.
+            let mut parse = benchmark.parse.as_ref().map(|parse| ns(parse.median));
+            for solve in &benchmark.solve {
+                let parse = parse.take().unwrap_or_else(|| "-".to_owned());
+                let parts = solve.parts.iter().map(u8::to_string).collect::<Vec<_>>().join("+");
+                let time = &solve.time;
+                println!(
+                    "{:>3} {parts:>4} {parse:>10}  {:>10} {:>10} {:>10} {:>10}",
+                    date.day,
+                    ns(time.min),
+                    ns(time.median),
+                    ns(time.mean),
+                    ns(time.stddev)
+                );
+            }
+            benchmarks.push(benchmark);
+        }
+    });
+
+    let total: Duration = benchmarks.iter().map(Benchmark::median).sum();
+    println!("Total median time for {year}: {total:.2?}");
+    (benchmarks, failures)
+}
+
+/// Rerun every part with a known answer (for the given year, if any) and report any whose answer
+/// has changed.
+///
+/// Parts that fail to run count as regressions, and either makes the process exit with a failure
+/// status at the end.
+fn verify(year: Option<u16>, account: &Account, cache: &Cache) {
+    let known = KnownAnswers::load(account, cache).unwrap_or_else(exit);
+    let known: Vec<_> = known.iter().filter(|(date, ..)| year.is_none_or(|year| date.year == year)).collect();
+    if known.is_empty() {
+        exit("No known answers to verify, submit or record some first")
+    }
+
+    let mut regressions = 0;
+    with_silenced_panics(|| {
+        for day in known.chunk_by(|(a, ..), (b, ..)| a == b) {
+            let date = day[0].0;
+            let result = solutions::find(date)
+                .map_err(|error| error.to_string())
+                .and_then(|solution| try_run_both(solution, read_cached_input(date, account, cache)?));
+            for &(_, part, expected) in day {
+                match &result {
+                    Ok(answers) if answers[part as usize].value().to_string() == expected => {
+                        println!("{date} part {part}: ok")
+                    },
+                    Ok(answers) => {
+                        regressions += 1;
+                        let answer = answers[part as usize].value();
+                        println!("{date} part {part}: REGRESSED, expected {expected} but got {answer}");
+                    },
+                    Err(error) => {
+                        regressions += 1;
+                        println!("{date} part {part}: FAILED: {error}");
+                    },
+                }
+            }
+        }
+    });
+
+    if regressions > 0 {
+        exit(format!("{regressions} of {} known answers regressed", known.len()))
+    }
+    println!("Verified {} known answers", known.len());
+}
+
+/// Record the answer as the known answer for one part of the given date.
+fn record(date: ChallengeDate, part: Part, answer: &aoc::Answer, account: &Account, cache: &Cache) {
+    let result = KnownAnswers::load(account, cache).and_then(|mut known| known.record(date, part, answer));
+    if let Err(error) = result {
+        eprintln!("failed to record known answer: {error}");
+    }
+}
+
+/// Run both parts of the solution on the input, which is only parsed once, catching any panic.
+fn try_run_both(solution: &Solution, input: String) -> Result<[aoc::Answer; 2], String> {
+    let input = Input::memory(input);
+    std::panic::catch_unwind(AssertUnwindSafe(|| solution.run_both(input))).map_err(panic_message)
+}
+
+/// Returns the message a solution panicked with.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    let message = match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload.downcast_ref::<&str>().map_or("unknown error", |message| message).to_owned(),
+    };
+    format!("panicked: {message}")
+}
+
+/// Get the input for the given date, from the given path (or stdin for `-`) if there is one.
+fn load_input(date: ChallengeDate, account: &Account, cache: &Cache, input: Option<&str>) -> Input {
+    match input {
+        Some("-") => Input::stdin(),
+        Some(path) => {
+            Input::file(path).unwrap_or_else(|error| exit(format!("failed to open input file {path}: {error}")))
+        },
+        None => Input::fetch(date, account, cache).unwrap_or_else(exit),
+    }
+}
+
+fn print_answer(part: Part, answer: &aoc::Answer) {
+    if let Some(artefact) = answer.artefact() {
+        println!("{artefact}");
+    }
+    println!("Part #{part}: {answer}");
+}
+
+/// Sleep until the challenge unlocks, showing a countdown.
+fn wait_for_unlock(date: ChallengeDate) {
+    // Give the server a moment after the unlock so our request doesn't beat it.
+    const GRACE: Duration = Duration::from_secs(1);
+
+    if date.is_unlocked() {
+        return;
+    }
+    while let Some(remaining) = date.until_unlock() {
+        let secs = remaining.as_secs();
+        eprint!("\rUnlocks in {:02}:{:02}:{:02} ", secs / 3600, secs / 60 % 60, secs % 60);
+        _ = std::io::stderr().flush();
+        std::thread::sleep(remaining.min(Duration::from_secs(1)));
+    }
+    eprintln!("\rUnlocked!{:10}", "");
+    std::thread::sleep(GRACE);
+}
+
+/// Fetch every missing input and summarise what happened.
+fn prefetch(dates: Vec<ChallengeDate>, account: &Account, cache: &Cache, delay: Duration) {
+    let summary = fetch::prefetch(dates, account, cache, delay).unwrap_or_else(exit);
+    for date in &summary.fetched {
+        println!("{date}: fetched");
+    }
+    for (date, reason) in &summary.skipped {
+        println!("{date}: skipped, {reason}");
+    }
+    for (date, error) in &summary.failed {
+        println!("{date}: failed, {error}");
+    }
+    println!("Fetched {}, skipped {}, failed {}", summary.fetched.len(), summary.skipped.len(), summary.failed.len());
+    if !summary.failed.is_empty() {
         std::process::exit(1);
     }
-    let year = format!("year_{}", args[1]);
-    let day = format!("day{}", args[2]);
-    let part = args[3].trim().parse::<u8>().unwrap();
-    solutions::run(&year, &day, part);
+}
+
+/// Print the account's personal leaderboard stats for the year next to how long the solutions take
+/// to run.
+fn print_stats(year: u16, account: &Account, cache: &Cache) {
+    let days = stats::fetch(year, account).unwrap_or_else(|error| exit(format!("{error:#}")));
+    if days.is_empty() {
+        exit(format!("No stars collected in {year}"))
+    }
+    println!(
+        "{:>3}  {:>8} {:>7} {:>5}  {:>8} {:>7} {:>5}  {:>10} {:>10}",
+        "Day", "Time", "Rank", "Score", "Time", "Rank", "Score", "Run 1", "Run 2"
+    );
+    with_silenced_panics(|| {
+        for day in days {
+            let date = ChallengeDate { year, day: day.day };
+            let [run1, run2] = Part::ALL.map(|part| {
+                time_run(date, part, account, cache).map_or_else(|| "-".to_owned(), |time| format!("{time:.2?}"))
+            });
+            let part2 = day.part2.map_or_else(
+                || format!("{:>8} {:>7} {:>5}", "-", "-", "-"),
+                |part2| format!("{:>8} {:>7} {:>5}", part2.time.to_string(), part2.rank, part2.score),
+            );
+            println!(
+                "{:>3}  {:>8} {:>7} {:>5}  {part2}  {run1:>10} {run2:>10}",
+                day.day,
+                day.part1.time.to_string(),
+                day.part1.rank,
+                day.part1.score,
+            );
+        }
+    });
+}
+
+/// Time one part of the solution for the given date, if it is implemented, its input is cached and
+/// it doesn't panic.
+fn time_run(date: ChallengeDate, part: Part, account: &Account, cache: &Cache) -> Option<Duration> {
+    let solution = solutions::find(date).ok()?;
+    let input = Input::memory(read_cached_input(date, account, cache).ok()?);
+    let started = Instant::now();
+    std::panic::catch_unwind(AssertUnwindSafe(|| solution.run(part, input))).ok()?;
+    Some(started.elapsed())
+}
+
+/// Run `f` with the panic hook silenced, for callers that catch panicking solutions and report
+/// them in their own output, then restore the previous hook.
+fn with_silenced_panics<T>(f: impl FnOnce() -> T) -> T {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    let result = f();
+    std::panic::set_hook(previous);
+    result
+}
+
+/// Read the cached input for the given date into memory, without fetching it if it is missing.
+fn read_cached_input(date: ChallengeDate, account: &Account, cache: &Cache) -> Result<String, String> {
+    match Input::cached(date, account, cache) {
+        Ok(Some(input)) => Ok(input.read_all()),
+        Ok(None) => Err(format!("{date} input is not cached, run `fetch` first")),
+        Err(error) => Err(error.to_string()),
+    }
+}
+
+/// Report cached inputs that are actually AoC error pages, optionally removing them.
+fn scan_cache(cache: &Cache, remove: bool) {
+    let poisoned = cache::scan_poisoned(cache).unwrap_or_else(exit);
+    for (path, page) in &poisoned {
+        println!("{}: {page}", path.display());
+        if remove {
+            cache::remove(path).unwrap_or_else(exit);
+        }
+    }
+    match (poisoned.len(), remove) {
+        (0, _) => println!("No poisoned entries in {}", cache.root().display()),
+        (count, true) => println!("Removed {count} poisoned entries"),
+        (count, false) => println!("Found {count} poisoned entries, rerun with --remove to delete them"),
+    }
+}
+
+/// Print the error and exit with a failure status.
+fn exit<T>(error: impl std::fmt::Display) -> T {
+    eprintln!("{error}");
+    std::process::exit(1);
 }
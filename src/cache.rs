@@ -0,0 +1,344 @@
+use std::path::{Path, PathBuf};
+use std::{fmt, fs, io};
+
+pub use encryption::CacheKey;
+
+use crate::account::Account;
+use crate::date::ChallengeDate;
+use crate::input::{ErrorPage, InputError};
+use crate::solutions::Part;
+
+mod encryption;
+
+/// Environment variable that overrides where cached data is stored.
+const CACHE_DIR_VAR: &str = "AOC_CACHE_DIR";
+
+/// Where fetched data is cached on disk.
+#[derive(Clone, Debug)]
+pub struct Cache {
+    root: PathBuf,
+    key: Option<CacheKey>,
+    report: Option<fn(&CacheWarning)>,
+}
+
+/// Something that went wrong with the cache without stopping the data from being read or fetched.
+#[derive(Debug)]
+pub enum CacheWarning {
+    /// The cached data was damaged, so it was removed to be fetched again.
+    Damaged(PathBuf),
+    /// Fetched input was not cached because AoC responded with an error page.
+    ErrorPage(ErrorPage),
+    /// Fetched input could not be written to the cache.
+    WriteFailed(io::Error),
+}
+
+impl fmt::Display for CacheWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Damaged(path) => write!(f, "cached input at {} is damaged, fetching it again", path.display()),
+            Self::ErrorPage(page) => write!(f, "not caching input data, AoC responded with {page}"),
+            Self::WriteFailed(error) => write!(f, "failed to cache input data: {error}"),
+        }
+    }
+}
+
+impl Cache {
+    /// Use the given directory as the cache root.
+    pub fn at(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into(), key: None, report: None }
+    }
+
+    /// Find the cache root, using the first of:
+    /// 1. The given override (e.g. from `--cache-dir`).
+    /// 2. The `AOC_CACHE_DIR` environment variable.
+    /// 3. `.cache` in the current directory, if it already exists.
+    /// 4. `$XDG_CACHE_HOME/aoc`, where `XDG_CACHE_HOME` defaults to `~/.cache`.
+    pub fn locate(root: Option<PathBuf>) -> Self {
+        const LEGACY_ROOT: &str = ".cache";
+
+        let root = root
+            .or_else(|| std::env::var_os(CACHE_DIR_VAR).filter(|dir| !dir.is_empty()).map(PathBuf::from))
+            .or_else(|| Path::new(LEGACY_ROOT).is_dir().then(|| PathBuf::from(LEGACY_ROOT)))
+            .or_else(|| {
+                std::env::var_os("XDG_CACHE_HOME")
+                    .filter(|dir| !dir.is_empty())
+                    .map(PathBuf::from)
+                    .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+                    .map(|dir| dir.join("aoc"))
+            })
+            .unwrap_or_else(|| PathBuf::from(LEGACY_ROOT));
+        Self { root, key: None, report: None }
+    }
+
+    /// Encrypt newly cached inputs with the given key (and decrypt existing ones with it).
+    pub fn with_key(self, key: Option<CacheKey>) -> Self {
+        Self { key, ..self }
+    }
+
+    /// Pass warnings about the cache to the given function (e.g. to print them). Without one,
+    /// they are ignored.
+    pub fn with_warnings(self, report: fn(&CacheWarning)) -> Self {
+        Self { report: Some(report), ..self }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn key(&self) -> Option<&CacheKey> {
+        self.key.as_ref()
+    }
+
+    pub(crate) fn warn(&self, warning: CacheWarning) {
+        if let Some(report) = self.report {
+            report(&warning);
+        }
+    }
+
+    /// Returns the path to the file that caches the account's input for the given challenge date.
+    pub fn input_path(&self, date: &ChallengeDate, account: &Account) -> PathBuf {
+        let file_name = format!("{}-{}.txt", date.year, date.day);
+        account.scope(&self.root).join(file_name)
+    }
+
+    /// Returns the path to the file that caches the private leaderboard, as seen by the account.
+    pub fn leaderboard_path(&self, year: u16, id: u64, account: &Account) -> PathBuf {
+        account.scope(&self.root).join("leaderboards").join(format!("{year}-{id}.json"))
+    }
+
+    /// Returns the path to the file that caches the challenge's description, as seen by the
+    /// account (which determines whether part 2 is shown).
+    pub fn description_path(&self, date: &ChallengeDate, account: &Account) -> PathBuf {
+        let file_name = format!("{}-{}.html", date.year, date.day);
+        account.scope(&self.root).join(file_name)
+    }
+
+    /// Returns the path to the file that records the account's submissions for one part of the
+    /// given challenge date.
+    pub fn ledger_path(&self, date: &ChallengeDate, part: Part, account: &Account) -> PathBuf {
+        let file_name = format!("{}-{}-{}.tsv", date.year, date.day, part);
+        account.scope(&self.root).join("submissions").join(file_name)
+    }
+
+    /// Returns the path to the file that records the answers AoC has accepted from the account.
+    pub fn known_answers_path(&self, account: &Account) -> PathBuf {
+        account.scope(&self.root).join("answers.tsv")
+    }
+}
+
+/// Write data to the cache file at the given path, along with a checksum of it, encrypting it if
+/// a key is given.
+///
+/// Both files are written atomically, checksum first, so a crash part way through leaves either
+/// the previous data or a checksum mismatch that triggers a refetch, never a truncated input.
+pub(crate) fn cache_all(file_path: &Path, data: &str, key: Option<&CacheKey>) -> io::Result<()> {
+    let data = match key {
+        Some(key) => key.encrypt(data.as_bytes()),
+        None => data.as_bytes().to_vec(),
+    };
+    let directory = file_path.parent().unwrap();
+    fs::create_dir_all(directory)?;
+    write_atomic(&checksum_path(file_path), checksum(&data).as_bytes())?;
+    write_atomic(file_path, &data)
+}
+
+/// Read data from the cache file at the given path, decrypting it with the cache's key if it is
+/// encrypted.
+///
+/// If the data does not match its checksum, the damaged file is removed and reported as a warning,
+/// and `None` is returned so that the input is fetched again.
+pub(crate) fn read_cached(file_path: &Path, cache: &Cache) -> Result<Option<Vec<u8>>, InputError> {
+    if !file_path.exists() {
+        return Ok(None);
+    }
+    let data = fs::read(file_path)?;
+
+    let expected = match fs::read_to_string(checksum_path(file_path)) {
+        Ok(expected) => Some(expected),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+        Err(error) => return Err(error.into()),
+    };
+    // Caches written before checksums existed are trusted as long as they are not empty.
+    let intact = match expected {
+        Some(expected) => expected.trim() == checksum(&data),
+        None => !data.is_empty(),
+    };
+    let data = match (intact && encryption::is_encrypted(&data), cache.key()) {
+        (false, _) => data,
+        (true, Some(key)) => key.decrypt(&data).ok_or_else(|| InputError::WrongCacheKey(file_path.to_owned()))?,
+        (true, None) => return Err(InputError::MissingCacheKey(file_path.to_owned())),
+    };
+    if !intact || ErrorPage::detect(&data).is_some() {
+        remove(file_path).map_err(|_| InputError::CorruptCache(file_path.to_owned()))?;
+        cache.warn(CacheWarning::Damaged(file_path.to_owned()));
+        return Ok(None);
+    }
+    Ok(Some(data))
+}
+
+/// Find cached inputs under the cache root (for every account) that are actually AoC error pages.
+///
+/// Encrypted inputs can only be checked if the cache has the key they were encrypted with.
+pub fn scan_poisoned(cache: &Cache) -> io::Result<Vec<(PathBuf, ErrorPage)>> {
+    let mut poisoned = Vec::new();
+    for path in cached_inputs(&cache.root)? {
+        let mut data = fs::read(&path)?;
+        if encryption::is_encrypted(&data) {
+            match cache.key.as_ref().and_then(|key| key.decrypt(&data)) {
+                Some(plaintext) => data = plaintext,
+                None => continue,
+            }
+        }
+        if let Some(page) = ErrorPage::detect(&data) {
+            poisoned.push((path, page));
+        }
+    }
+    Ok(poisoned)
+}
+
+/// Encrypt every plaintext input under the cache root (for every account) in place, with the
+/// cache's key, returning the paths that were encrypted.
+///
+/// Damaged inputs are removed rather than encrypted, as they would be when read.
+pub fn encrypt_in_place(cache: &Cache) -> Result<Vec<PathBuf>, InputError> {
+    let key = cache.key.as_ref().ok_or_else(|| InputError::MissingCacheKey(cache.root.clone()))?;
+    let mut encrypted = Vec::new();
+    for path in cached_inputs(&cache.root)? {
+        if encryption::is_encrypted(&fs::read(&path)?) {
+            continue;
+        }
+        let Some(data) = read_cached(&path, cache)? else {
+            continue;
+        };
+        let data = String::from_utf8(data).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        cache_all(&path, &data, Some(key))?;
+        encrypted.push(path);
+    }
+    Ok(encrypted)
+}
+
+/// Returns the path of every cached input under the root, sorted.
+fn cached_inputs(root: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut inputs = Vec::new();
+    let mut directories = vec![root.to_owned()];
+    while let Some(directory) = directories.pop() {
+        let entries = match fs::read_dir(&directory) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => continue,
+            Err(error) => return Err(error),
+        };
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_dir() {
+                directories.push(path);
+            } else if path.extension().is_some_and(|extension| extension == "txt") {
+                inputs.push(path);
+            }
+        }
+    }
+    inputs.sort();
+    Ok(inputs)
+}
+
+/// Remove a cached input along with its checksum.
+pub fn remove(file_path: &Path) -> io::Result<()> {
+    fs::remove_file(file_path)?;
+    match fs::remove_file(checksum_path(file_path)) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+/// Write to a temporary file next to the destination and then rename it into place.
+fn write_atomic(file_path: &Path, data: &[u8]) -> io::Result<()> {
+    let mut temp_name = file_path.file_name().unwrap_or_default().to_owned();
+    temp_name.push(format!(".tmp-{}", std::process::id()));
+    let temp_path = file_path.with_file_name(temp_name);
+    fs::write(&temp_path, data)?;
+    fs::rename(&temp_path, file_path).inspect_err(|_| {
+        _ = fs::remove_file(&temp_path);
+    })
+}
+
+/// Returns the path of the checksum stored alongside a cache file.
+fn checksum_path(file_path: &Path) -> PathBuf {
+    let mut file_name = file_path.file_name().unwrap_or_default().to_owned();
+    file_name.push(".sum");
+    file_path.with_file_name(file_name)
+}
+
+/// Returns the length and 64-bit FNV-1a hash of the data.
+fn checksum(data: &[u8]) -> String {
+    const OFFSET_BASIS: u64 = 0xCBF29CE484222325;
+    const PRIME: u64 = 0x100000001B3;
+
+    let hash = data.iter().fold(OFFSET_BASIS, |hash, &byte| (hash ^ u64::from(byte)).wrapping_mul(PRIME));
+    format!("{} {hash:016x}", data.len())
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+    use std::io::Write;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::mock::TempDir;
+
+    #[test]
+    fn refetches_damaged_cache() {
+        static DAMAGED: AtomicUsize = AtomicUsize::new(0);
+
+        let root = TempDir::new("cache");
+        let cache = Cache::at(root.path()).with_warnings(|warning| {
+            assert!(matches!(warning, CacheWarning::Damaged(_)));
+            DAMAGED.fetch_add(1, Ordering::Relaxed);
+        });
+        let path = cache.input_path(&ChallengeDate { year: 2024, day: 1 }, &Account::named("work").unwrap());
+        assert!(path.starts_with(root.path().join("work")));
+
+        cache_all(&path, "1 2\n3 4\n", None).unwrap();
+        assert_eq!(read_cached(&path, &cache).unwrap().unwrap(), b"1 2\n3 4\n");
+        assert_eq!(DAMAGED.load(Ordering::Relaxed), 0);
+
+        // Simulate a crash that left the input truncated.
+        File::create(&path).unwrap().write_all(b"1 2\n").unwrap();
+        assert!(read_cached(&path, &cache).unwrap().is_none());
+        assert!(!path.exists());
+        assert_eq!(DAMAGED.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn finds_poisoned_entries() {
+        let root = TempDir::new("cache-scan");
+        let cache = Cache::at(root.path());
+        let good = cache.input_path(&ChallengeDate { year: 2024, day: 1 }, &Account::default());
+        let bad = cache.input_path(&ChallengeDate { year: 2024, day: 2 }, &Account::named("work").unwrap());
+        cache_all(&good, "1 2\n", None).unwrap();
+        fs::create_dir_all(bad.parent().unwrap()).unwrap();
+        fs::write(&bad, "Please don't repeatedly request this endpoint before it unlocks!").unwrap();
+
+        assert_eq!(scan_poisoned(&cache).unwrap(), vec![(bad.clone(), ErrorPage::NotYetUnlocked)]);
+        remove(&bad).unwrap();
+        assert!(scan_poisoned(&cache).unwrap().is_empty());
+    }
+
+    #[test]
+    fn encrypts_existing_cache_in_place() {
+        let root = TempDir::new("cache-encrypt");
+        let path = Cache::at(root.path()).input_path(&ChallengeDate { year: 2024, day: 1 }, &Account::default());
+        cache_all(&path, "1 2\n", None).unwrap();
+
+        let key = CacheKey::from_passphrase("hunter2");
+        let cache = Cache::at(root.path()).with_key(Some(key.clone()));
+        assert_eq!(encrypt_in_place(&cache).unwrap(), vec![path.clone()]);
+        assert!(encrypt_in_place(&cache).unwrap().is_empty());
+        assert!(!fs::read(&path).unwrap().starts_with(b"1 2"));
+
+        assert_eq!(read_cached(&path, &cache).unwrap().unwrap(), b"1 2\n");
+        assert!(matches!(read_cached(&path, &Cache::at(root.path())), Err(InputError::MissingCacheKey(_))));
+        let wrong = Cache::at(root.path()).with_key(Some(CacheKey::from_passphrase("hunter3")));
+        assert!(matches!(read_cached(&path, &wrong), Err(InputError::WrongCacheKey(_))));
+        assert!(path.exists());
+    }
+}
@@ -0,0 +1,123 @@
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+use std::{fmt, io};
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use sha2::Sha256;
+
+/// Environment variable holding the passphrase to encrypt the cache with.
+const KEY_VAR: &str = "AOC_CACHE_KEY";
+/// Environment variable holding the path to a file containing the passphrase.
+const KEY_FILE_VAR: &str = "AOC_CACHE_KEY_FILE";
+
+/// Marks the start of an encrypted cache file, followed by the nonce and then the ciphertext.
+const MAGIC: &[u8] = b"aoc-encrypted-v1\n";
+/// Salt for deriving keys from passphrases.
+///
+/// The salt is fixed so the key only has to be derived once, rather than once per file.
+const SALT: &[u8] = b"advent-of-code input cache";
+const ROUNDS: u32 = 100_000;
+const NONCE_LEN: usize = 12;
+
+/// The key cached inputs are encrypted with, so they can be shared without publishing them.
+///
+/// Deriving the key is deliberately slow, so it is only done the first time the key is used, and
+/// clones share the derived key.
+#[derive(Clone)]
+pub struct CacheKey {
+    passphrase: Arc<str>,
+    key: Arc<OnceLock<Key>>,
+}
+
+impl CacheKey {
+    /// Use the key derived from a passphrase.
+    pub fn from_passphrase(passphrase: &str) -> Self {
+        Self { passphrase: passphrase.into(), key: Arc::default() }
+    }
+
+    fn key(&self) -> &Key {
+        self.key
+            .get_or_init(|| pbkdf2::pbkdf2_hmac_array::<Sha256, 32>(self.passphrase.as_bytes(), SALT, ROUNDS).into())
+    }
+
+    /// Read the passphrase from the `AOC_CACHE_KEY` environment variable or, failing that, the
+    /// file named by `AOC_CACHE_KEY_FILE`.
+    ///
+    /// Returns `None` if neither is set, in which case the cache is stored in plaintext.
+    pub fn from_env() -> io::Result<Option<Self>> {
+        if let Some(passphrase) = std::env::var(KEY_VAR).ok().filter(|passphrase| !passphrase.is_empty()) {
+            return Ok(Some(Self::from_passphrase(&passphrase)));
+        }
+        let Some(path) = std::env::var_os(KEY_FILE_VAR).filter(|path| !path.is_empty()).map(PathBuf::from) else {
+            return Ok(None);
+        };
+        let passphrase = std::fs::read_to_string(&path).map_err(|error| {
+            io::Error::new(error.kind(), format!("failed to read cache key from {}: {error}", path.display()))
+        })?;
+        Ok(Some(Self::from_passphrase(passphrase.trim())))
+    }
+
+    pub(crate) fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = ChaCha20Poly1305::new(self.key())
+            .encrypt(&nonce, plaintext)
+            .expect("plaintext is too long to encrypt, this is a programmer error");
+        [MAGIC, nonce.as_slice(), &ciphertext].concat()
+    }
+
+    /// Decrypt data written by [`CacheKey::encrypt`], returning `None` if it was encrypted with a
+    /// different key or has been tampered with.
+    pub(crate) fn decrypt(&self, data: &[u8]) -> Option<Vec<u8>> {
+        let data = data.strip_prefix(MAGIC)?;
+        if data.len() < NONCE_LEN {
+            return None;
+        }
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        ChaCha20Poly1305::new(self.key()).decrypt(Nonce::from_slice(nonce), ciphertext).ok()
+    }
+}
+
+impl PartialEq for CacheKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.passphrase == other.passphrase
+    }
+}
+
+impl Eq for CacheKey {}
+
+impl fmt::Debug for CacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CacheKey(..)")
+    }
+}
+
+/// Returns whether the cached data is encrypted.
+pub(crate) fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trips_with_matching_key_only() {
+        let key = CacheKey::from_passphrase("hunter2");
+        let encrypted = key.encrypt(b"1 2\n3 4\n");
+        assert!(is_encrypted(&encrypted));
+        assert_ne!(encrypted, key.encrypt(b"1 2\n3 4\n"));
+        assert_eq!(key.decrypt(&encrypted).unwrap(), b"1 2\n3 4\n");
+        assert!(CacheKey::from_passphrase("hunter3").decrypt(&encrypted).is_none());
+        assert!(key.decrypt(b"1 2\n3 4\n").is_none());
+    }
+
+    #[test]
+    fn derives_key_on_first_use() {
+        let key = CacheKey::from_passphrase("hunter2");
+        let clone = key.clone();
+        assert!(key.key.get().is_none());
+        key.encrypt(b"");
+        assert!(clone.key.get().is_some());
+    }
+}
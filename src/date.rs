@@ -1,8 +1,236 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// Offset of US Eastern time from UTC in December, when daylight saving time is never in effect.
+const EASTERN_OFFSET_HOURS: u64 = 5;
+/// The year of the first event.
+const FIRST_YEAR: u16 = 2015;
+
+lazy_static! {
+    /// Matches `2024/7`, `2024-07`, `24d7` and `2024 day 7`.
+    static ref DATE_REGEX: Regex =
+        Regex::new(r"^(?:(\d{4})[/-](\d{1,2})|(\d{2})d(\d{1,2})|(\d{4}) day (\d{1,2}))$").unwrap();
+}
+
 /// A date that has an AoC challenge.
 ///
 /// Month is irrelevant in this context because the AoC challenge occurs yearly
 /// in December.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct ChallengeDate {
     pub year: u16,
     pub day: u8,
 }
+
+impl ChallengeDate {
+    /// Returns the date, if AoC has (or will this year) run a challenge on it.
+    pub fn new(year: u16, day: u8) -> Result<Self, InvalidDate> {
+        if !Self::years().contains(&year) {
+            return Err(InvalidDate::Year(year));
+        }
+        if !(1..=Self::days_in(year)).contains(&day) {
+            return Err(InvalidDate::Day(year, day));
+        }
+        Ok(Self { year, day })
+    }
+
+    /// Parse a year that AoC has (or will this year) run a challenge in, like `2024` or `24`.
+    pub fn parse_year(s: &str) -> Result<u16, InvalidDate> {
+        let s = s.trim();
+        let year = match s.len() {
+            2 | 4 => s.parse().map_err(|_| InvalidDate::YearFormat(s.to_owned()))?,
+            _ => return Err(InvalidDate::YearFormat(s.to_owned())),
+        };
+        let year = expand_year(year);
+        Self::new(year, 1).map(|_| year)
+    }
+
+    /// Returns the challenge that unlocked most recently, if an event is running.
+    pub fn today() -> Option<Self> {
+        Self::on(SystemTime::now())
+    }
+
+    /// Returns the challenge that was the most recent to unlock at the given time, if an event
+    /// was running.
+    fn on(time: SystemTime) -> Option<Self> {
+        let secs = time.duration_since(UNIX_EPOCH).ok()?.as_secs();
+        let days = secs.checked_sub(EASTERN_OFFSET_HOURS * 3600)? / 86400;
+        let year = year_of(days);
+        let day = u8::try_from(days.checked_sub(days_since_epoch(year, 12, 1))? + 1).ok()?;
+        Self::new(year, day).ok()
+    }
+
+    /// Returns every year AoC has run, including this year even if its event has not started.
+    pub fn years() -> RangeInclusive<u16> {
+        let days = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs()) / 86400;
+        FIRST_YEAR..=year_of(days)
+    }
+
+    /// Returns the number of days in the given year's event.
+    ///
+    /// Events ran for 25 days until 2025, when they were shortened to 12.
+    pub fn days_in(year: u16) -> u8 {
+        if year >= 2025 {
+            12
+        } else {
+            25
+        }
+    }
+
+    /// Returns every challenge date in the given year's event.
+    pub fn all_in(year: u16) -> impl Iterator<Item = Self> {
+        (1..=Self::days_in(year)).map(move |day| Self { year, day })
+    }
+
+    /// Returns the instant the challenge unlocks, which is midnight US Eastern time.
+    pub fn unlock_time(&self) -> SystemTime {
+        let days = days_since_epoch(self.year, 12, self.day);
+        UNIX_EPOCH + Duration::from_secs(days * 86400 + EASTERN_OFFSET_HOURS * 3600)
+    }
+
+    /// Returns how long until the challenge unlocks, or `None` if it already has.
+    pub fn until_unlock(&self) -> Option<Duration> {
+        self.unlock_time().duration_since(SystemTime::now()).ok().filter(|remaining| !remaining.is_zero())
+    }
+
+    /// Returns whether the challenge has unlocked.
+    pub fn is_unlocked(&self) -> bool {
+        self.until_unlock().is_none()
+    }
+}
+
+impl fmt::Display for ChallengeDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
+    }
+}
+
+impl FromStr for ChallengeDate {
+    type Err = InvalidDate;
+
+    /// Parse a date like `2024/7`, `2024-07`, `24d7` or `2024 day 7`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let captures = DATE_REGEX.captures(s.trim()).ok_or_else(|| InvalidDate::Format(s.to_owned()))?;
+        let mut groups = captures.iter().skip(1).flatten().map(|group| group.as_str());
+        let (year, day) = (groups.next().unwrap(), groups.next().unwrap());
+        Self::new(expand_year(year.parse().unwrap()), day.parse().unwrap())
+    }
+}
+
+/// Returned when a date does not have an AoC challenge, or cannot be parsed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InvalidDate {
+    Format(String),
+    YearFormat(String),
+    Year(u16),
+    Day(u16, u8),
+}
+
+impl fmt::Display for InvalidDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Format(s) => write!(f, "invalid date {s:?}, expected e.g. 2024/7, 2024-07 or 24d7"),
+            Self::YearFormat(s) => write!(f, "invalid year {s:?}, expected e.g. 2024 or 24"),
+            Self::Year(year) => {
+                let years = ChallengeDate::years();
+                write!(f, "AoC has not run in {year}, it runs from {} to {}", years.start(), years.end())
+            },
+            Self::Day(year, day) => {
+                write!(f, "{year} has no day {day}, it has days 1 to {}", ChallengeDate::days_in(*year))
+            },
+        }
+    }
+}
+
+impl std::error::Error for InvalidDate {}
+
+/// Expands a two digit year like `24` to `2024`.
+fn expand_year(year: u16) -> u16 {
+    if year < 100 {
+        2000 + year
+    } else {
+        year
+    }
+}
+
+/// Returns the year of the date that is the given number of days after the Unix epoch.
+fn year_of(days: u64) -> u16 {
+    // Estimate the year from the average length of a year, then correct it.
+    let year = (1970 + days * 400 / 146097) as u16;
+    if days < days_since_epoch(year, 1, 1) {
+        year - 1
+    } else if days >= days_since_epoch(year + 1, 1, 1) {
+        year + 1
+    } else {
+        year
+    }
+}
+
+/// Returns the number of days between the Unix epoch and the given (proleptic Gregorian) date.
+///
+/// This is Howard Hinnant's `days_from_civil` algorithm, restricted to dates after the epoch.
+fn days_since_epoch(year: u16, month: u8, day: u8) -> u64 {
+    let (month, day) = (u64::from(month), u64::from(day));
+    let year = u64::from(year) - u64::from(month <= 2);
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unlocks_at_midnight_eastern() {
+        let unlock = |year, day| ChallengeDate { year, day }.unlock_time().duration_since(UNIX_EPOCH).unwrap();
+        assert_eq!(unlock(2024, 1).as_secs(), 1733029200);
+        assert_eq!(unlock(2015, 25).as_secs(), 1451019600);
+        assert!(ChallengeDate { year: 2015, day: 1 }.is_unlocked());
+    }
+
+    #[test]
+    fn parses_and_displays() {
+        let date = ChallengeDate { year: 2024, day: 7 };
+        for s in ["2024/7", "2024-07", "24d7", "2024 day 7"] {
+            assert_eq!(s.parse(), Ok(date));
+        }
+        assert_eq!(date.to_string().parse(), Ok(date));
+        assert_eq!("2024.7".parse::<ChallengeDate>(), Err(InvalidDate::Format("2024.7".to_owned())));
+        assert_eq!("2014/1".parse::<ChallengeDate>(), Err(InvalidDate::Year(2014)));
+        assert_eq!("2024/26".parse::<ChallengeDate>(), Err(InvalidDate::Day(2024, 26)));
+        assert_eq!("2025/13".parse::<ChallengeDate>(), Err(InvalidDate::Day(2025, 13)));
+        assert!(ChallengeDate { year: 2023, day: 25 } < date);
+
+        assert_eq!(ChallengeDate::parse_year("2024"), Ok(2024));
+        assert_eq!(ChallengeDate::parse_year("24"), Ok(2024));
+        assert_eq!(ChallengeDate::parse_year("2014"), Err(InvalidDate::Year(2014)));
+        assert_eq!(ChallengeDate::parse_year("202"), Err(InvalidDate::YearFormat("202".to_owned())));
+    }
+
+    #[test]
+    fn resolves_today_in_eastern_time() {
+        let at = |secs| ChallengeDate::on(UNIX_EPOCH + Duration::from_secs(secs));
+        // 2024-12-01 04:59:59 UTC is still November 30th in New York.
+        assert_eq!(at(1733029199), None);
+        assert_eq!(at(1733029200), Some(ChallengeDate { year: 2024, day: 1 }));
+        // 2024-12-26 05:00 UTC, after the last day.
+        assert_eq!(at(1735189200), None);
+        // 2016-12-31 04:00 UTC is still the 30th in New York, but past the event.
+        assert_eq!(at(1483156800), None);
+        assert_eq!(at(1482642000 - 1), Some(ChallengeDate { year: 2016, day: 24 }));
+    }
+
+    #[test]
+    fn event_lengths() {
+        assert_eq!(ChallengeDate::all_in(2024).last(), Some(ChallengeDate { year: 2024, day: 25 }));
+        assert_eq!(ChallengeDate::all_in(2025).count(), 12);
+    }
+}
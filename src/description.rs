@@ -0,0 +1,367 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use reqwest::StatusCode;
+use url::Url;
+
+use crate::account::Account;
+use crate::cache::{cache_all, read_cached, Cache};
+use crate::date::ChallengeDate;
+use crate::input::{base_url, build_page_url, client, ErrorPage, InputError};
+use crate::ledger::Ledger;
+use crate::solutions::Part;
+use crate::submit::Verdict;
+
+lazy_static! {
+    /// Matches each part's description on a day's challenge page.
+    static ref ARTICLE_REGEX: Regex = Regex::new(r#"(?s)<article class="day-desc">.*?</article>"#).unwrap();
+    /// Matches an HTML tag, capturing whether it closes, its name and its attributes.
+    static ref TAG_REGEX: Regex = Regex::new(r"<(/?)([a-zA-Z0-9]+)([^>]*)>").unwrap();
+    static ref HREF_REGEX: Regex = Regex::new(r#"href="([^"]*)""#).unwrap();
+    /// Matches a preformatted code block, which is how example inputs are shown.
+    static ref PRE_REGEX: Regex = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+    /// Matches emphasised code, which is how example answers are shown.
+    static ref ANSWER_REGEX: Regex =
+        Regex::new(r"<code><em>([^<]*)</em></code>|<em><code>([^<]*)</code></em>").unwrap();
+}
+
+/// The example from one part's description.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Example {
+    pub part: Part,
+    pub input: String,
+    /// The answer for the example input, if one was found.
+    pub answer: Option<String>,
+}
+
+impl Example {
+    /// Write the example as fixture files for the `examples!` macro, `part<n>.input` and
+    /// `part<n>.answer`, returning the paths written.
+    pub fn write_fixture(&self, directory: &Path) -> io::Result<Vec<PathBuf>> {
+        fs::create_dir_all(directory)?;
+        let input_path = directory.join(format!("part{}.input", self.part));
+        fs::write(&input_path, &self.input)?;
+        let mut written = vec![input_path];
+        if let Some(answer) = &self.answer {
+            let answer_path = directory.join(format!("part{}.answer", self.part));
+            fs::write(&answer_path, format!("{answer}\n"))?;
+            written.push(answer_path);
+        }
+        Ok(written)
+    }
+}
+
+/// Returns the directory that holds the example fixtures for the given challenge date.
+pub fn fixture_dir(date: &ChallengeDate) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures").join(date.year.to_string()).join(format!("day{}", date.day))
+}
+
+/// How to render a description.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Format {
+    /// Plain text with ANSI styling.
+    Terminal,
+    Markdown,
+}
+
+/// The description of a day's challenge, one article per unlocked part.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Description {
+    articles: Vec<String>,
+}
+
+impl Description {
+    /// Get the description for the given day's challenge, fetching it from AoC's website as the
+    /// given account unless it is already in the cache.
+    ///
+    /// The cached description is refreshed if it only has part 1 but the ledger shows part 1 has
+    /// been solved (so part 2 is now unlocked), or if `refresh` is set.
+    pub fn fetch(date: ChallengeDate, account: &Account, cache: &Cache, refresh: bool) -> Result<Self, InputError> {
+        Self::fetch_from(&base_url()?, date, account, cache, refresh)
+    }
+
+    pub(crate) fn fetch_from(
+        base: &Url,
+        date: ChallengeDate,
+        account: &Account,
+        cache: &Cache,
+        refresh: bool,
+    ) -> Result<Self, InputError> {
+        let cache_path = cache.description_path(&date, account);
+        if let Some(data) = read_cached(&cache_path, cache)? {
+            let cached = Self::parse(&String::from_utf8_lossy(&data));
+            if !refresh && !(cached.articles.len() == 1 && part1_solved(date, account, cache)) {
+                return Ok(cached);
+            }
+        }
+        if !date.is_unlocked() {
+            return Err(InputError::NotYetUnlocked(date));
+        }
+
+        let url = build_page_url(base, &[&date.year.to_string(), "day", &date.day.to_string()]);
+        let mut request = client().get(url);
+        // Part 2 is only shown to accounts that have solved part 1, but part 1 is public.
+        if let Ok(session) = account.session_token() {
+            request = request.header("Cookie", format!("session={session}"));
+        }
+        let response = request.send()?;
+        match response.status() {
+            status if status.is_success() => {},
+            StatusCode::BAD_REQUEST => return Err(InputError::ExpiredSession),
+            StatusCode::NOT_FOUND => return Err(InputError::NotYetUnlocked(date)),
+            status => return Err(InputError::Status(status)),
+        }
+
+        let description = Self::parse(&response.text()?);
+        if description.articles.is_empty() {
+            return Err(InputError::ErrorPage(ErrorPage::Html));
+        }
+        cache_all(&cache_path, &description.articles.join("\n"), cache.key())?;
+        Ok(description)
+    }
+
+    /// Extract the description from a challenge page (or a cached description).
+    pub fn parse(html: &str) -> Self {
+        Self { articles: ARTICLE_REGEX.find_iter(html).map(|article| article.as_str().to_owned()).collect() }
+    }
+
+    /// Returns the number of parts that are unlocked.
+    pub fn parts(&self) -> usize {
+        self.articles.len()
+    }
+
+    /// Pull out the example of each unlocked part.
+    ///
+    /// The example input is taken to be the first code block of the part (part 2 usually reuses
+    /// part 1's), and the answer the last emphasised code. These are heuristics, so check the
+    /// results.
+    pub fn examples(&self) -> Vec<Example> {
+        let mut examples: Vec<Example> = Vec::new();
+        for (article, part) in self.articles.iter().zip(Part::ALL) {
+            let input = PRE_REGEX
+                .captures(article)
+                .map(|pre| strip_tags(&pre[1]))
+                .or_else(|| examples.last().map(|example| example.input.clone()));
+            let answer = ANSWER_REGEX
+                .captures_iter(article)
+                .last()
+                .and_then(|answer| answer.get(1).or(answer.get(2)))
+                .map(|answer| decode_entities(answer.as_str()));
+            if let Some(input) = input {
+                examples.push(Example { part, input, answer });
+            }
+        }
+        examples
+    }
+
+    pub fn render(&self, format: Format) -> String {
+        self.articles.iter().map(|article| render(article, format)).collect::<Vec<_>>().join("\n")
+    }
+}
+
+/// Returns whether the ledger shows that part 1 has been solved.
+fn part1_solved(date: ChallengeDate, account: &Account, cache: &Cache) -> bool {
+    Ledger::load(&date, Part::One, account, cache)
+        .is_ok_and(|ledger| ledger.entries().iter().any(|entry| entry.verdict == Verdict::Correct))
+}
+
+/// Render an article's HTML as text in the given format.
+fn render(html: &str, format: Format) -> String {
+    let markdown = format == Format::Markdown;
+    let mut out = String::new();
+    let (mut in_pre, mut in_code, mut em_in_code) = (false, false, false);
+    let mut href = None;
+    let mut last = 0;
+    for tag in TAG_REGEX.captures_iter(html) {
+        let whole = tag.get(0).unwrap();
+        push_text(&mut out, &html[last..whole.start()], in_pre);
+        last = whole.end();
+
+        let closing = !tag[1].is_empty();
+        match (tag[2].to_ascii_lowercase().as_str(), closing) {
+            ("h2", false) => out.push_str(if markdown { "## " } else { "\x1B[1m" }),
+            ("h2", true) => out.push_str(if markdown { "\n\n" } else { "\x1B[22m\n\n" }),
+            ("p", true) | ("ul", true) => out.push_str("\n\n"),
+            ("p", false) | ("ul", false) => {},
+            ("li", false) => out.push_str(if markdown { "- " } else { "  • " }),
+            ("li", true) => out.push('\n'),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str(if markdown { "```\n" } else { "" });
+            },
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str(if markdown { "```\n\n" } else { "\n" });
+            },
+            ("code", false) if in_pre => {},
+            ("code", true) if in_pre => {},
+            ("code", false) => {
+                in_code = true;
+                out.push_str(if markdown { "`" } else { "\x1B[36m" });
+            },
+            ("code", true) => {
+                in_code = false;
+                if markdown && em_in_code {
+                    em_in_code = false;
+                    out.push_str("`*");
+                } else {
+                    out.push_str(if markdown { "`" } else { "\x1B[39m" });
+                }
+            },
+            // Markdown has no emphasis inside code, so emphasise the whole span when it is the
+            // entire code span (e.g. `<code><em>42</em></code>`) and drop it otherwise.
+            ("em", false) if markdown && in_code => {
+                if out.ends_with('`') {
+                    out.insert(out.len() - 1, '*');
+                    em_in_code = true;
+                }
+            },
+            ("em", true) if markdown && in_code => {},
+            ("em", _) if markdown && in_pre => {},
+            ("em", _) if markdown => out.push('*'),
+            ("em", false) => out.push_str("\x1B[1m"),
+            ("em", true) => out.push_str("\x1B[22m"),
+            ("a", false) => {
+                href = HREF_REGEX.captures(&tag[3]).map(|href| href[1].to_owned());
+                if markdown && href.is_some() {
+                    out.push('[');
+                }
+            },
+            ("a", true) => {
+                if let Some(href) = href.take() {
+                    let href = base_url()
+                        .ok()
+                        .and_then(|base| base.join(&decode_entities(&href)).ok())
+                        .map_or(href, |url| url.to_string());
+                    if markdown {
+                        _ = write!(out, "]({href})");
+                    } else {
+                        _ = write!(out, " ({href})");
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+    push_text(&mut out, &html[last..], in_pre);
+    format!("{}\n", out.trim_end())
+}
+
+/// Append text from between tags, collapsing whitespace unless it is preformatted.
+fn push_text(out: &mut String, text: &str, in_pre: bool) {
+    let text = decode_entities(text);
+    if in_pre {
+        out.push_str(&text);
+        return;
+    }
+    let mut words = text.split_whitespace().peekable();
+    if text.starts_with(char::is_whitespace) && words.peek().is_some() && !out.is_empty() && !out.ends_with('\n') {
+        out.push(' ');
+    }
+    while let Some(word) = words.next() {
+        out.push_str(word);
+        if words.peek().is_some() {
+            out.push(' ');
+        }
+    }
+    if text.ends_with(char::is_whitespace) && !text.trim().is_empty() {
+        out.push(' ');
+    }
+}
+
+/// Remove the tags from HTML, leaving the decoded text.
+pub(crate) fn strip_tags(html: &str) -> String {
+    decode_entities(&TAG_REGEX.replace_all(html, ""))
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::{MockServer, TempDir};
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us"><body><main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>Pair up the <em>smallest</em> numbers, e.g. <code>3 &lt; 4</code>.</p>
+<pre><code>3   4
+4   <em>3</em>
+</code></pre>
+<p>What is the <a href="/2024/day/1/input">total distance</a>? It is <code><em>11</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>1234</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><ul><li>One</li><li>Two</li></ul></article>
+</main></body></html>"#;
+
+    #[test]
+    fn extracts_each_part() {
+        let description = Description::parse(PAGE);
+        assert_eq!(description.parts(), 2);
+        assert_eq!(Description::parse(&description.articles.join("\n")), description);
+    }
+
+    #[test]
+    fn fetches_and_caches_description() {
+        let root = TempDir::new("description");
+        let (cache, account) = (Cache::at(root.path()), Account::named("description").unwrap().with_session("abc"));
+        let date = ChallengeDate { year: 2024, day: 1 };
+        let part1 = &PAGE[..PAGE.find("<article class=\"day-desc\"><h2 id=\"part2\">").unwrap()];
+        let server = MockServer::start();
+        for page in [part1, part1, PAGE] {
+            server.route("GET", "/2024/day/1", 200, page);
+        }
+
+        let fetched = Description::fetch_from(server.url(), date, &account, &cache, false).unwrap();
+        assert_eq!(Description::fetch_from(server.url(), date, &account, &cache, false).unwrap(), fetched);
+        assert_eq!(server.requests().len(), 1);
+        Description::fetch_from(server.url(), date, &account, &cache, true).unwrap();
+        assert_eq!(server.requests().len(), 2);
+
+        // Solving part 1 unlocks part 2, so the cached copy with only part 1 is refreshed.
+        let mut ledger = Ledger::load(&date, Part::One, &account, &cache).unwrap();
+        ledger.record(&crate::Answer::from(11_u32), Verdict::Correct).unwrap();
+        assert_eq!(Description::fetch_from(server.url(), date, &account, &cache, false).unwrap().parts(), 2);
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[test]
+    fn extracts_examples() {
+        let examples = Description::parse(PAGE).examples();
+        assert_eq!(examples, vec![
+            Example { part: Part::One, input: "3   4\n4   3\n".to_owned(), answer: Some("11".to_owned()) },
+            Example { part: Part::Two, input: "3   4\n4   3\n".to_owned(), answer: None },
+        ]);
+    }
+
+    #[test]
+    fn renders_markdown() {
+        assert_eq!(
+            Description::parse(PAGE).render(Format::Markdown),
+            "## --- Day 1: Historian Hysteria ---\n\nPair up the *smallest* numbers, e.g. `3 < 4`.\n\n```\n3   4\n4   \
+             3\n```\n\nWhat is the [total distance](https://adventofcode.com/2024/day/1/input)? It is *`11`*.\n\n## \
+             --- Part Two ---\n\n- One\n- Two\n"
+        );
+    }
+
+    #[test]
+    fn renders_terminal_text() {
+        let rendered = render(Description::parse(PAGE).articles[0].as_str(), Format::Terminal);
+        assert!(rendered.starts_with("\x1B[1m--- Day 1: Historian Hysteria ---\x1B[22m\n\n"));
+        assert!(rendered.contains("Pair up the \x1B[1msmallest\x1B[22m numbers, e.g. \x1B[36m3 < 4\x1B[39m."));
+        assert!(rendered.contains("3   4\n4   \x1B[1m3\x1B[22m\n\n"));
+        assert!(rendered.contains("total distance (https://adventofcode.com/2024/day/1/input)?"));
+    }
+}
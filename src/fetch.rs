@@ -0,0 +1,183 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use url::Url;
+
+use crate::account::Account;
+use crate::cache::{read_cached, Cache};
+use crate::date::ChallengeDate;
+use crate::input::{base_url, Input, InputError};
+
+/// Why a challenge's input was not fetched.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Skipped {
+    /// The input is already in the cache.
+    Cached,
+    /// The challenge has not been unlocked yet.
+    Locked,
+    /// Not attempted because an earlier request showed the session cannot be used.
+    Aborted,
+}
+
+impl fmt::Display for Skipped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cached => write!(f, "already cached"),
+            Self::Locked => write!(f, "not unlocked yet"),
+            Self::Aborted => write!(f, "not attempted"),
+        }
+    }
+}
+
+/// What happened to each challenge date when prefetching inputs.
+#[derive(Debug, Default)]
+pub struct Summary {
+    pub fetched: Vec<ChallengeDate>,
+    pub skipped: Vec<(ChallengeDate, Skipped)>,
+    pub failed: Vec<(ChallengeDate, InputError)>,
+}
+
+/// Makes sure requests are at least a minimum delay apart.
+struct Throttle {
+    delay: Duration,
+    last: Option<Instant>,
+}
+
+impl Throttle {
+    fn new(delay: Duration) -> Self {
+        Self { delay, last: None }
+    }
+
+    /// Sleep until the delay has passed since the previous request.
+    fn wait(&mut self) {
+        if let Some(last) = self.last {
+            std::thread::sleep(self.delay.saturating_sub(last.elapsed()));
+        }
+        self.last = Some(Instant::now());
+    }
+}
+
+/// Fetch the input for every given challenge date that is unlocked and missing from the cache,
+/// waiting at least `delay` between requests to AoC.
+///
+/// Fetching stops early if the account's session is missing or expired, since every remaining
+/// request would fail the same way.
+pub fn prefetch(
+    dates: impl IntoIterator<Item = ChallengeDate>,
+    account: &Account,
+    cache: &Cache,
+    delay: Duration,
+) -> Result<Summary, InputError> {
+    Ok(prefetch_from(&base_url()?, dates, account, cache, delay))
+}
+
+pub(crate) fn prefetch_from(
+    base: &Url,
+    dates: impl IntoIterator<Item = ChallengeDate>,
+    account: &Account,
+    cache: &Cache,
+    delay: Duration,
+) -> Summary {
+    let mut summary = Summary::default();
+    let mut throttle = Throttle::new(delay);
+    let mut dates = dates.into_iter();
+    for date in dates.by_ref() {
+        let cache_path = cache.input_path(&date, account);
+        match read_cached(&cache_path, cache) {
+            Ok(Some(_)) => {
+                summary.skipped.push((date, Skipped::Cached));
+                continue;
+            },
+            Ok(None) => {},
+            Err(error) => {
+                summary.failed.push((date, error));
+                continue;
+            },
+        }
+        if !date.is_unlocked() {
+            summary.skipped.push((date, Skipped::Locked));
+            continue;
+        }
+
+        throttle.wait();
+        let result = Input::fetch_from(base, date, account, cache).and_then(|input| Ok(input.drain()?));
+        match result {
+            Ok(()) if cache_path.exists() => summary.fetched.push(date),
+            Ok(()) => summary.failed.push((date, InputError::CorruptCache(cache_path))),
+            Err(error @ (InputError::MissingSession { .. } | InputError::ExpiredSession)) => {
+                summary.failed.push((date, error));
+                break;
+            },
+            Err(error) => summary.failed.push((date, error)),
+        }
+    }
+    summary.skipped.extend(dates.map(|date| (date, Skipped::Aborted)));
+    summary
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cache::cache_all;
+    use crate::mock::{MockServer, TempDir};
+
+    #[test]
+    fn skips_cached_and_locked_without_requests() {
+        let root = TempDir::new("prefetch");
+        let cache = Cache::at(root.path());
+        let account = Account::named("prefetch").unwrap();
+        let cached = ChallengeDate { year: 2015, day: 1 };
+        cache_all(&cache.input_path(&cached, &account), "(()(()(\n", None).unwrap();
+        let locked = ChallengeDate { year: u16::MAX / 16, day: 1 };
+
+        let started = Instant::now();
+        let summary = prefetch([cached, locked], &account, &cache, Duration::from_secs(60)).unwrap();
+        assert!(started.elapsed() < Duration::from_secs(60));
+        assert!(summary.fetched.is_empty() && summary.failed.is_empty());
+        assert_eq!(summary.skipped, vec![(cached, Skipped::Cached), (locked, Skipped::Locked)]);
+    }
+
+    #[test]
+    fn fetches_missing_inputs_politely() {
+        let root = TempDir::new("prefetch-fetch");
+        let (cache, account) = (Cache::at(root.path()), Account::default().with_session("abc"));
+        let server = MockServer::start();
+        server.route("GET", "/2015/day/1/input", 200, "(()\n").route("GET", "/2015/day/3/input", 200, "^>v<\n");
+        let dates = [1, 2, 3].map(|day| ChallengeDate { year: 2015, day });
+
+        let started = Instant::now();
+        let summary = prefetch_from(server.url(), dates, &account, &cache, Duration::from_millis(100));
+        assert!(started.elapsed() >= Duration::from_millis(200));
+        assert_eq!(summary.fetched, [dates[0], dates[2]]);
+        assert!(matches!(summary.failed.as_slice(), [(date, InputError::NotYetUnlocked(_))] if *date == dates[1]));
+        assert!(cache.input_path(&dates[2], &account).exists());
+
+        let summary = prefetch_from(server.url(), dates, &account, &cache, Duration::ZERO);
+        assert_eq!(summary.skipped, [(dates[0], Skipped::Cached), (dates[2], Skipped::Cached)]);
+        assert_eq!(server.requests().len(), 4);
+    }
+
+    #[test]
+    fn stops_when_session_is_expired() {
+        let root = TempDir::new("prefetch-expired");
+        let (cache, account) = (Cache::at(root.path()), Account::default().with_session("abc"));
+        let server = MockServer::start();
+        let dates = [1, 2].map(|day| ChallengeDate { year: 2015, day });
+        server.route("GET", "/2015/day/1/input", 400, "");
+
+        let summary = prefetch_from(server.url(), dates, &account, &cache, Duration::ZERO);
+        assert!(matches!(summary.failed.as_slice(), [(_, InputError::ExpiredSession)]));
+        assert_eq!(summary.skipped, [(dates[1], Skipped::Aborted)]);
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn throttle_spaces_out_requests() {
+        let mut throttle = Throttle::new(Duration::from_millis(50));
+        let started = Instant::now();
+        throttle.wait();
+        assert!(started.elapsed() < Duration::from_millis(50));
+        throttle.wait();
+        assert!(started.elapsed() >= Duration::from_millis(50));
+    }
+}
@@ -1,30 +1,207 @@
-use std::fs::{self, File};
-use std::io::{self, BufRead, BufReader, Cursor, Read, Write};
-use std::path::PathBuf;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Cursor, Read, StdinLock};
+use std::path::{Path, PathBuf};
 
 use reqwest::blocking::{Client, Response};
+use reqwest::StatusCode;
 use url::Url;
 
+use crate::account::Account;
+use crate::cache::{cache_all, read_cached, Cache, CacheWarning};
 use crate::date::ChallengeDate;
 
+/// Errors that can occur while getting the input for a challenge.
+#[derive(Debug)]
+pub enum InputError {
+    /// There is no session token to authenticate with.
+    MissingSession {
+        account: String,
+        var: String,
+    },
+    /// AoC rejected the session token (400 Bad Request).
+    ExpiredSession,
+    /// The challenge has not been unlocked yet.
+    NotYetUnlocked(ChallengeDate),
+    /// AoC responded with some other unsuccessful status.
+    Status(StatusCode),
+    /// The request to AoC could not be completed.
+    Http(reqwest::Error),
+    Io(io::Error),
+    /// The cached input exists but cannot be used.
+    CorruptCache(PathBuf),
+    /// The cached input is encrypted but no cache key is set.
+    MissingCacheKey(PathBuf),
+    /// The cached input could not be decrypted with the cache key.
+    WrongCacheKey(PathBuf),
+    /// AoC responded with a page that is not puzzle input.
+    ErrorPage(ErrorPage),
+    /// `AOC_BASE_URL` is set to something that cannot be used as the root of AoC's website.
+    BaseUrl {
+        url: String,
+        reason: String,
+    },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSession { account, var } => write!(
+                f,
+                "no session token found for the {account} account, set {var} or save your adventofcode.com session \
+                 cookie to the config directory or session.txt"
+            ),
+            Self::ExpiredSession => {
+                write!(f, "session cookie expired, refresh session.txt (or wherever the token is stored)")
+            },
+            Self::NotYetUnlocked(date) => write!(f, "{date} has not been unlocked yet"),
+            Self::Status(status) => write!(f, "non-200 status returned when fetching input data: {status}"),
+            Self::Http(error) => write!(f, "failed to get AoC input data: {error}"),
+            Self::Io(error) => write!(f, "failed to read input data: {error}"),
+            Self::ErrorPage(page) => write!(f, "AoC responded with {page} instead of puzzle input"),
+            Self::MissingCacheKey(path) => write!(
+                f,
+                "cached input at {} is encrypted, set AOC_CACHE_KEY or AOC_CACHE_KEY_FILE to read it",
+                path.display()
+            ),
+            Self::WrongCacheKey(path) => {
+                write!(f, "cached input at {} was encrypted with a different key", path.display())
+            },
+            Self::CorruptCache(path) => {
+                write!(f, "cached input at {} is corrupt, delete it to fetch the input again", path.display())
+            },
+            Self::BaseUrl { url, reason } => write!(f, "bad AOC_BASE_URL {url}: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Http(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for InputError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<reqwest::Error> for InputError {
+    fn from(error: reqwest::Error) -> Self {
+        Self::Http(error)
+    }
+}
+
+/// A response from AoC that looks like it could be puzzle input, but is not.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ErrorPage {
+    /// The request was made before the challenge unlocked.
+    NotYetUnlocked,
+    /// The session was not accepted, so AoC asks to log in.
+    LoggedOut,
+    /// Some other HTML page.
+    Html,
+}
+
+impl ErrorPage {
+    /// Recognise an error page from the start of a response.
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        let text = String::from_utf8_lossy(&data[..data.len().min(512)]);
+        let text = text.trim_start();
+        if text.starts_with("Please don't repeatedly request this endpoint before it unlocks") {
+            Some(Self::NotYetUnlocked)
+        } else if text.starts_with("Puzzle inputs differ by user") {
+            Some(Self::LoggedOut)
+        } else if text.starts_with('<') && text.to_ascii_lowercase().contains("<html") {
+            Some(Self::Html)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for ErrorPage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotYetUnlocked => write!(f, "a request to wait until the challenge unlocks"),
+            Self::LoggedOut => write!(f, "a prompt to log in"),
+            Self::Html => write!(f, "an HTML page"),
+        }
+    }
+}
+
 pub trait Reader: Read + BufRead {}
 
 impl<T> Reader for T where T: Read + BufRead {}
 
+/// Passes through everything read from the inner reader while keeping a copy of it, which is
+/// written to the cache once the inner reader reaches EOF.
+///
+/// If the input is not read to the end, or reading fails, nothing is cached. Input that can't be
+/// cached is reported as a warning through the cache rather than failing the read.
+struct TeeReader<R> {
+    inner: R,
+    cache_path: PathBuf,
+    cache: Cache,
+    data: Vec<u8>,
+    committed: bool,
+}
+
+impl<R> TeeReader<R> {
+    fn new(inner: R, cache_path: PathBuf, cache: Cache) -> Self {
+        Self { inner, cache_path, cache, data: Vec::new(), committed: false }
+    }
+
+    fn commit(&mut self) {
+        self.committed = true;
+        if let Some(page) = ErrorPage::detect(&self.data) {
+            self.cache.warn(CacheWarning::ErrorPage(page));
+            return;
+        }
+        let result = std::str::from_utf8(&self.data)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+            .and_then(|data| cache_all(&self.cache_path, data, self.cache.key()));
+        if let Err(error) = result {
+            self.cache.warn(CacheWarning::WriteFailed(error));
+        }
+    }
+}
+
+impl<R: Read> Read for TeeReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let count = self.inner.read(buf)?;
+        if count > 0 {
+            self.data.extend_from_slice(&buf[..count]);
+        } else if !buf.is_empty() && !self.committed {
+            self.commit();
+        }
+        Ok(count)
+    }
+}
+
 /// Tagged wrapper for the input data `BufReader`.
 enum InputSource {
-    Website(BufReader<Response>),
-    Cache(BufReader<File>),
+    Website(Box<BufReader<TeeReader<Response>>>),
+    Cache(Cursor<Vec<u8>>),
     Memory(BufReader<Cursor<String>>),
+    File(BufReader<File>),
+    Stdin(StdinLock<'static>),
 }
 
 impl InputSource {
     /// Get a mutable reference to the inner `BufReader`.
-    fn inner_mut(&mut self) -> Box<&mut dyn Reader> {
+    fn inner_mut(&mut self) -> &mut dyn Reader {
         match self {
-            Self::Website(reader) => Box::new(reader),
-            Self::Cache(reader) => Box::new(reader),
-            Self::Memory(reader) => Box::new(reader),
+            Self::Website(reader) => reader.as_mut(),
+            Self::Cache(reader) => reader,
+            Self::Memory(reader) => reader,
+            Self::File(reader) => reader,
+            Self::Stdin(reader) => reader,
         }
     }
 
@@ -34,110 +211,273 @@ impl InputSource {
             Self::Website(reader) => Box::new(reader),
             Self::Cache(reader) => Box::new(reader),
             Self::Memory(reader) => Box::new(reader),
+            Self::File(reader) => Box::new(reader),
+            Self::Stdin(reader) => Box::new(reader),
         }
     }
-
-    /// Returns whether the variant is `Self::Website(_)`.
-    fn is_website(&self) -> bool {
-        matches!(self, InputSource::Website(_))
-    }
 }
 
 /// Input data for a challenge.
 pub struct Input {
-    date: Option<ChallengeDate>,
     source: InputSource,
 }
 
 impl Input {
-    /// Fetch the input for the given day's challenge from AoC's website.
-    pub fn fetch(date: ChallengeDate) -> Self {
-        let source = if let Some(file_reader) = read_cached(&date) {
-            InputSource::Cache(file_reader)
+    /// Fetch the input for the given day's challenge from AoC's website, as the given account,
+    /// unless it is already in the cache.
+    pub fn fetch(date: ChallengeDate, account: &Account, cache: &Cache) -> Result<Self, InputError> {
+        Self::fetch_from(&base_url()?, date, account, cache)
+    }
+
+    pub(crate) fn fetch_from(
+        base: &Url,
+        date: ChallengeDate,
+        account: &Account,
+        cache: &Cache,
+    ) -> Result<Self, InputError> {
+        let cache_path = cache.input_path(&date, account);
+        let source = if let Some(data) = read_cached(&cache_path, cache)? {
+            InputSource::Cache(Cursor::new(data))
         } else {
-            let url = build_url(&date);
-            let session = fetch_session_token();
-            let response = Client::new()
-                .get(url)
-                .header("Cookie", format!("session={session}"))
-                .send()
-                .expect("failed to get AoC input data");
-
-            let status = response.status();
-            if !status.is_success() {
-                panic!("non-200 status returned when fetching input data: {status}");
+            if !date.is_unlocked() {
+                return Err(InputError::NotYetUnlocked(date));
+            }
+            let url = build_url(base, &date);
+            let session = account.session_token()?;
+            let response = client().get(url).header("Cookie", format!("session={session}")).send()?;
+
+            match response.status() {
+                status if status.is_success() => {},
+                StatusCode::BAD_REQUEST => return Err(InputError::ExpiredSession),
+                StatusCode::NOT_FOUND => return Err(InputError::NotYetUnlocked(date)),
+                status => return Err(InputError::Status(status)),
             }
 
-            InputSource::Website(BufReader::new(response))
+            let mut reader = BufReader::new(TeeReader::new(response, cache_path, cache.clone()));
+            match ErrorPage::detect(reader.fill_buf()?) {
+                Some(ErrorPage::NotYetUnlocked) => return Err(InputError::NotYetUnlocked(date)),
+                Some(ErrorPage::LoggedOut) => return Err(InputError::ExpiredSession),
+                Some(page) => return Err(InputError::ErrorPage(page)),
+                None => {},
+            }
+
+            InputSource::Website(Box::new(reader))
         };
 
-        Self { date: Some(date), source }
+        Ok(Self { source })
+    }
+
+    /// Read the input for the given day's challenge from the cache, without fetching it if it is
+    /// not there.
+    pub fn cached(date: ChallengeDate, account: &Account, cache: &Cache) -> Result<Option<Self>, InputError> {
+        let data = read_cached(&cache.input_path(&date, account), cache)?;
+        Ok(data.map(|data| Self { source: InputSource::Cache(Cursor::new(data)) }))
     }
 
     /// Seed with the given input data.
     pub fn memory(data: impl Into<String>) -> Self {
-        Self { date: None, source: InputSource::Memory(BufReader::new(Cursor::new(data.into()))) }
+        Self { source: InputSource::Memory(BufReader::new(Cursor::new(data.into()))) }
+    }
+
+    /// Read the input from the file at the given path.
+    pub fn file(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self { source: InputSource::File(BufReader::new(File::open(path)?)) })
+    }
+
+    /// Read the input from stdin.
+    pub fn stdin() -> Self {
+        Self { source: InputSource::Stdin(io::stdin().lock()) }
     }
 
     /// Reads the entirety of the buffer to a string.
     pub fn read_all(mut self) -> String {
         let mut buf = String::new();
         _ = self.source.inner_mut().read_to_string(&mut buf).unwrap();
-        if self.source.is_website() {
-            if let Some(date) = &self.date {
-                if let Err(error) = cache_all(date, &buf) {
-                    eprintln!("failed to cache input data: {:?}", error);
-                }
-            }
-        }
         buf
     }
 
+    /// Reads the rest of the input without keeping it, which caches input from the website.
+    pub(crate) fn drain(mut self) -> io::Result<()> {
+        io::copy(self.source.inner_mut(), &mut io::sink()).map(drop)
+    }
+
     /// Reads the input line-by-line.
     pub fn read_lines(self) -> impl Iterator<Item = String> {
         self.source.into_inner().lines().map(Result::unwrap)
     }
 }
 
-/// Returns the path to the file that caches input for the given challenge date.
-fn cache_path(date: &ChallengeDate) -> PathBuf {
-    let file_name = format!("{}-{}.txt", date.year, date.day);
-    PathBuf::new().join(".cache").join(file_name)
-}
+/// Returns an HTTP client that identifies this tool to AoC, as requested for automated access.
+pub(crate) fn client() -> Client {
+    const USER_AGENT: &str =
+        concat!("github.com/mathew-horner/advent-of-code ", env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 
-/// Write data to cache file for the given challenge date.
-fn cache_all(date: &ChallengeDate, data: &str) -> io::Result<()> {
-    let file_path = cache_path(&date);
-    let directory = file_path.parent().unwrap();
-    fs::create_dir_all(directory)?;
-    let mut file = File::create(file_path)?;
-    file.write_all(data.as_bytes())?;
-    Ok(())
+    Client::builder().user_agent(USER_AGENT).build().expect("failed to build HTTP client")
 }
 
-/// Read data from cache file for the given challenge date.
-fn read_cached(date: &ChallengeDate) -> Option<BufReader<File>> {
-    let file_path = cache_path(&date);
-    if !file_path.exists() {
-        return None;
+/// Returns the root URL of AoC's website, which can be overridden with the `AOC_BASE_URL`
+/// environment variable (e.g. to point at a mock server).
+pub(crate) fn base_url() -> Result<Url, InputError> {
+    const BASE_URL: &str = "https://adventofcode.com";
+
+    match std::env::var("AOC_BASE_URL") {
+        Ok(url) if !url.is_empty() => match Url::parse(&url) {
+            Ok(parsed) if parsed.cannot_be_a_base() => {
+                Err(InputError::BaseUrl { url, reason: "cannot have paths under it".to_owned() })
+            },
+            Ok(parsed) => Ok(parsed),
+            Err(error) => Err(InputError::BaseUrl { url, reason: error.to_string() }),
+        },
+        _ => Ok(Url::parse(BASE_URL).expect("bad BASE_URL, this is a programmer error")),
     }
-    let file = File::open(file_path).unwrap();
-    Some(BufReader::new(file))
+}
+
+/// Builds the URL of a page on AoC's website from its path segments (e.g. `["2024", "day", "7"]`),
+/// keeping any path the base URL already has.
+pub(crate) fn build_page_url(base: &Url, segments: &[&str]) -> Url {
+    let mut url = base.clone();
+    url.path_segments_mut()
+        .expect("base URL cannot be a base, this is a programmer error")
+        .pop_if_empty()
+        .extend(segments);
+    url
+}
+
+/// Builds the URL of an endpoint under the given day's challenge page (e.g.
+/// `/2024/day/7/input`).
+pub(crate) fn build_day_url(base: &Url, date: &ChallengeDate, endpoint: &str) -> Url {
+    build_page_url(base, &[&date.year.to_string(), "day", &date.day.to_string(), endpoint])
 }
 
 /// Builds the URL to get the input for the given day's challenge from AoC's
 /// website.
-fn build_url(date: &ChallengeDate) -> Url {
-    const BASE_URL: &str = "https://adventofcode.com";
+fn build_url(base: &Url, date: &ChallengeDate) -> Url {
+    build_day_url(base, date, "input")
+}
 
-    let mut url = Url::parse(BASE_URL).expect("bad BASE_URL, this is a programmer error");
-    url.path_segments_mut().unwrap().extend([&date.year.to_string(), "day", &date.day.to_string(), "input"]);
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
-    url
-}
+    use super::*;
+    use crate::mock::{MockServer, TempDir};
+
+    #[test]
+    fn builds_urls_under_base_path() {
+        let date = ChallengeDate { year: 2024, day: 7 };
+        for base in ["http://localhost/aoc", "http://localhost/aoc/"] {
+            let url = build_day_url(&Url::parse(base).unwrap(), &date, "input");
+            assert_eq!(url.as_str(), "http://localhost/aoc/2024/day/7/input");
+        }
+        let url = build_page_url(&Url::parse("https://adventofcode.com").unwrap(), &["2024", "leaderboard", "self"]);
+        assert_eq!(url.as_str(), "https://adventofcode.com/2024/leaderboard/self");
+    }
+
+    #[test]
+    fn detects_error_pages() {
+        let unlock = b"Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is \
+                       synchronized with the server time; the link will be enabled on the calendar the instant this \
+                       puzzle becomes available.\n";
+        assert_eq!(ErrorPage::detect(unlock), Some(ErrorPage::NotYetUnlocked));
+        let logged_out = b"Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+        assert_eq!(ErrorPage::detect(logged_out), Some(ErrorPage::LoggedOut));
+        assert_eq!(ErrorPage::detect(b"<!DOCTYPE html>\n<html lang=\"en-us\">"), Some(ErrorPage::Html));
+        assert_eq!(ErrorPage::detect(b"<<^>>v\n#..#\n"), None);
+        assert_eq!(ErrorPage::detect(b"3   4\n4   3\n"), None);
+    }
+
+    #[test]
+    fn fetches_once_then_reads_from_cache() {
+        let root = TempDir::new("input-fetch");
+        let (cache, account) = (Cache::at(root.path()), Account::default().with_session("abc"));
+        let server = MockServer::start();
+        server.fixture("GET", "/2024/day/8/input", "2024/day8/part1.input");
+        let date = ChallengeDate { year: 2024, day: 8 };
+
+        assert!(Input::cached(date, &account, &cache).unwrap().is_none());
+        let fetched = Input::fetch_from(server.url(), date, &account, &cache).unwrap().read_all();
+        let cached = Input::fetch_from(server.url(), date, &account, &cache).unwrap().read_all();
+        assert_eq!(fetched, cached);
+        assert_eq!(Input::cached(date, &account, &cache).unwrap().unwrap().read_all(), fetched);
+        assert!(fetched.starts_with("............\n"));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].header("cookie"), Some("session=abc"));
+        assert!(requests[0].header("user-agent").is_some());
+    }
 
-/// Fetches the stored token in `session.txt`.
-fn fetch_session_token() -> String {
-    let session = std::fs::read_to_string("session.txt").expect("failed to read session.txt file");
-    session.trim().to_owned()
+    #[test]
+    fn maps_error_responses() {
+        let root = TempDir::new("input-errors");
+        let (cache, account) = (Cache::at(root.path()), Account::default().with_session("abc"));
+        let server = MockServer::start();
+        server
+            .route("GET", "/2024/day/1/input", 400, "")
+            .route("GET", "/2024/day/2/input", 404, "")
+            .route(
+                "GET",
+                "/2024/day/3/input",
+                200,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+            )
+            .route("GET", "/2024/day/4/input", 500, "");
+        let fetch = |day| Input::fetch_from(server.url(), ChallengeDate { year: 2024, day }, &account, &cache);
+
+        assert!(matches!(fetch(1), Err(InputError::ExpiredSession)));
+        assert!(matches!(fetch(2), Err(InputError::NotYetUnlocked(_))));
+        assert!(matches!(fetch(3), Err(InputError::ExpiredSession)));
+        assert!(matches!(fetch(4), Err(InputError::Status(StatusCode::INTERNAL_SERVER_ERROR))));
+        assert!(!root.path().exists());
+
+        // Challenges that have not unlocked are never requested.
+        let locked = ChallengeDate { year: 9999, day: 1 };
+        assert!(matches!(
+            Input::fetch_from(server.url(), locked, &account, &cache),
+            Err(InputError::NotYetUnlocked(_))
+        ));
+        assert_eq!(server.requests().len(), 4);
+    }
+
+    #[test]
+    fn reads_from_file() {
+        let path = std::env::temp_dir().join(format!("aoc-input-{}.txt", std::process::id()));
+        fs::write(&path, "3   4\n4   3\n").unwrap();
+        assert_eq!(Input::file(&path).unwrap().read_lines().collect::<Vec<_>>(), ["3   4", "4   3"]);
+        fs::remove_file(path).unwrap();
+        assert!(Input::file(std::env::temp_dir().join("aoc-input-missing.txt")).is_err());
+    }
+
+    #[test]
+    fn tee_refuses_to_cache_error_pages() {
+        static REFUSED: AtomicUsize = AtomicUsize::new(0);
+
+        let root = TempDir::new("tee-poison");
+        let cache = Cache::at(root.path()).with_warnings(|warning| {
+            assert!(matches!(warning, CacheWarning::ErrorPage(ErrorPage::LoggedOut)));
+            REFUSED.fetch_add(1, Ordering::Relaxed);
+        });
+        let path = root.path().join("2024-1.txt");
+        let data = "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+        assert_eq!(BufReader::new(TeeReader::new(Cursor::new(data), path.clone(), cache)).lines().count(), 1);
+        assert!(!path.exists());
+        assert_eq!(REFUSED.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn tee_caches_on_eof_only() {
+        let root = TempDir::new("tee");
+        let path = root.path().join("2024-1.txt");
+        let data = "3   4\n4   3\n2   5\n";
+        let cache = Cache::at(root.path());
+
+        let partial = BufReader::new(TeeReader::new(Cursor::new(data), path.clone(), cache.clone()));
+        assert_eq!(partial.lines().next().unwrap().unwrap(), "3   4");
+        assert!(!path.exists());
+
+        let full = BufReader::new(TeeReader::new(Cursor::new(data), path.clone(), cache));
+        assert_eq!(full.lines().count(), 3);
+        assert_eq!(fs::read_to_string(&path).unwrap(), data);
+    }
 }
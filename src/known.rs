@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::{fs, io};
+
+use crate::account::Account;
+use crate::cache::Cache;
+use crate::date::ChallengeDate;
+use crate::ledger::{escape, unescape};
+use crate::solutions::Part;
+use crate::Answer;
+
+/// The answers AoC has accepted for an account, used to check solutions for regressions.
+pub struct KnownAnswers {
+    path: PathBuf,
+    answers: BTreeMap<(ChallengeDate, Part), String>,
+}
+
+impl KnownAnswers {
+    /// Load the account's known answers.
+    pub fn load(account: &Account, cache: &Cache) -> io::Result<Self> {
+        Self::open(cache.known_answers_path(account))
+    }
+
+    fn open(path: PathBuf) -> io::Result<Self> {
+        let answers = match fs::read_to_string(&path) {
+            Ok(contents) => contents.lines().filter_map(parse_line).collect(),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(error) => return Err(error),
+        };
+        Ok(Self { path, answers })
+    }
+
+    /// Returns the known answer for one part of a day's challenge.
+    pub fn get(&self, date: ChallengeDate, part: Part) -> Option<&str> {
+        self.answers.get(&(date, part)).map(String::as_str)
+    }
+
+    /// Returns every known answer, ordered by date and part.
+    pub fn iter(&self) -> impl Iterator<Item = (ChallengeDate, Part, &str)> {
+        self.answers.iter().map(|(&(date, part), answer)| (date, part, answer.as_str()))
+    }
+
+    /// Record the accepted answer for one part of a day's challenge and persist it, replacing any
+    /// previously known answer.
+    pub fn record(&mut self, date: ChallengeDate, part: Part, answer: &Answer) -> io::Result<()> {
+        self.answers.insert((date, part), answer.value().to_string());
+        if let Some(directory) = self.path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        let contents: String = self
+            .answers
+            .iter()
+            .map(|((date, part), answer)| format!("{}\t{}\t{part}\t{}\n", date.year, date.day, escape(answer)))
+            .collect();
+        fs::write(&self.path, contents)
+    }
+}
+
+/// Parse a line of `<year>\t<day>\t<part>\t<answer>`.
+fn parse_line(line: &str) -> Option<((ChallengeDate, Part), String)> {
+    let mut tokens = line.splitn(4, '\t');
+    let year = tokens.next()?.parse().ok()?;
+    let day = tokens.next()?.parse().ok()?;
+    let part = Part::try_from(tokens.next()?.parse::<u8>().ok()?).ok()?;
+    let answer = unescape(tokens.next()?)?;
+    Some(((ChallengeDate { year, day }, part), answer))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_file() {
+        let path = std::env::temp_dir().join(format!("aoc-known-{}.tsv", std::process::id()));
+        _ = fs::remove_file(&path);
+        let (day1, day2) = (ChallengeDate { year: 2024, day: 1 }, ChallengeDate { year: 2024, day: 2 });
+        let mut known = KnownAnswers::open(path.clone()).unwrap();
+        known.record(day2, Part::One, &Answer::from(7_u32)).unwrap();
+        known.record(day1, Part::Two, &Answer::rendered(12_u32, "#.#")).unwrap();
+        known.record(day1, Part::Two, &Answer::from("A\tB\nC".to_owned())).unwrap();
+
+        let reloaded = KnownAnswers::open(path.clone()).unwrap();
+        assert_eq!(reloaded.get(day1, Part::Two), Some("A\tB\nC"));
+        assert_eq!(reloaded.get(day1, Part::One), None);
+        assert_eq!(reloaded.iter().collect::<Vec<_>>(), [(day1, Part::Two, "A\tB\nC"), (day2, Part::One, "7")]);
+        fs::remove_file(path).unwrap();
+    }
+}
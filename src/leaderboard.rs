@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{anyhow, Context};
+use serde::Deserialize;
+use url::Url;
+
+use crate::account::Account;
+use crate::cache::{cache_all, read_cached, Cache};
+use crate::date::ChallengeDate;
+use crate::input::{base_url, build_page_url, client};
+
+/// AoC asks that private leaderboards are fetched at most once every 15 minutes.
+const MIN_REFRESH: Duration = Duration::from_secs(15 * 60);
+
+/// A private leaderboard, as returned by AoC's JSON API.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Leaderboard {
+    #[serde(deserialize_with = "year_from_str")]
+    pub event: u16,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Anonymous users have no name.
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    /// The time each star was earned, keyed by day and then part.
+    pub completion_day_level: HashMap<u8, HashMap<u8, Star>>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Star {
+    /// Seconds since the Unix epoch.
+    pub get_star_ts: u64,
+}
+
+impl Member {
+    /// Returns the member's name, or how AoC shows anonymous users.
+    pub fn display_name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    /// Returns how long after the challenge unlocked the member earned the star for the part, if
+    /// they have.
+    pub fn star_time(&self, date: &ChallengeDate, part: u8) -> Option<Duration> {
+        let star = self.completion_day_level.get(&date.day)?.get(&part)?;
+        let earned = SystemTime::UNIX_EPOCH + Duration::from_secs(star.get_star_ts);
+        Some(earned.duration_since(date.unlock_time()).unwrap_or_default())
+    }
+}
+
+impl Leaderboard {
+    /// Get the private leaderboard with the given ID for the year, as the given account.
+    ///
+    /// The leaderboard is only fetched again once the cached copy is 15 minutes old.
+    pub fn fetch(year: u16, id: u64, account: &Account, cache: &Cache) -> anyhow::Result<Self> {
+        Self::fetch_from(&base_url()?, year, id, account, cache)
+    }
+
+    pub(crate) fn fetch_from(base: &Url, year: u16, id: u64, account: &Account, cache: &Cache) -> anyhow::Result<Self> {
+        let cache_path = cache.leaderboard_path(year, id, account);
+        if is_fresh(&cache_path) {
+            if let Some(data) = read_cached(&cache_path, cache)? {
+                return Self::parse(&data);
+            }
+        }
+        download(base, &account.session_token()?, year, id, &cache_path, cache)
+    }
+
+    pub fn parse(json: &[u8]) -> anyhow::Result<Self> {
+        serde_json::from_slice(json).context("failed to parse leaderboard")
+    }
+
+    /// Returns the members, ordered by local score, then by stars, then by when they earned their
+    /// last star.
+    pub fn ranked(&self) -> Vec<&Member> {
+        let mut members: Vec<_> = self.members.values().collect();
+        members.sort_by_key(|member| {
+            let last_star = member.completion_day_level.values().flat_map(|parts| parts.values());
+            let last_star = last_star.map(|star| star.get_star_ts).max().unwrap_or(u64::MAX);
+            (std::cmp::Reverse(member.local_score), std::cmp::Reverse(member.stars), last_star, member.id)
+        });
+        members
+    }
+
+    /// Returns the latest day anyone has earned a star on.
+    pub fn latest_day(&self) -> Option<u8> {
+        self.members.values().flat_map(|member| member.completion_day_level.keys()).copied().max()
+    }
+
+    /// Render the leaderboard as a table with each member's stars for every day, local score and
+    /// completion times for the given day. See [`Leaderboard::render_times`] for every day's times.
+    pub fn render(&self, day: u8) -> String {
+        let date = ChallengeDate { year: self.event, day };
+        let days = ChallengeDate::all_in(self.event).count() as u8;
+        let mut out = String::new();
+        let day_header: String = (1..=days).map(|day| char::from(b'0' + day % 10)).collect();
+        let [part1, part2] = [1, 2].map(|part| format!("{day}/{part}"));
+        _ = writeln!(out, "{:>4} {:>5}  {day_header}  {part1:>10} {part2:>10}  Name", "", "Score");
+        for (rank, member) in self.ranked().into_iter().enumerate() {
+            let stars: String = (1..=days)
+                .map(|day| match member.completion_day_level.get(&day).map_or(0, HashMap::len) {
+                    0 => '.',
+                    1 => '+',
+                    _ => '*',
+                })
+                .collect();
+            let times = [1, 2].map(|part| member.star_time(&date, part).map_or_else(|| "-".to_owned(), format_time));
+            _ = writeln!(
+                out,
+                "{:>4} {:>5}  {stars}  {:>10} {:>10}  {}",
+                format!("{})", rank + 1),
+                member.local_score,
+                times[0],
+                times[1],
+                member.display_name()
+            );
+        }
+        out
+    }
+
+    /// Render a table of how long after unlock each member earned their stars, with a row for each
+    /// day the member earned a star on, ordered by day and then by rank.
+    pub fn render_times(&self) -> String {
+        let ranked = self.ranked();
+        let mut out = String::new();
+        _ = writeln!(out, "{:>3}  {:>10} {:>10}  Name", "Day", "Part 1", "Part 2");
+        for date in ChallengeDate::all_in(self.event) {
+            for member in ranked.iter().filter(|member| member.completion_day_level.contains_key(&date.day)) {
+                let times =
+                    [1, 2].map(|part| member.star_time(&date, part).map_or_else(|| "-".to_owned(), format_time));
+                _ = writeln!(out, "{:>3}  {:>10} {:>10}  {}", date.day, times[0], times[1], member.display_name());
+            }
+        }
+        out
+    }
+}
+
+fn download(
+    base: &Url,
+    session: &str,
+    year: u16,
+    id: u64,
+    cache_path: &Path,
+    cache: &Cache,
+) -> anyhow::Result<Leaderboard> {
+    let url = build_page_url(base, &[&year.to_string(), "leaderboard", "private", "view", &format!("{id}.json")]);
+    let response = client()
+        .get(url)
+        .header("Cookie", format!("session={session}"))
+        .send()
+        .context("failed to fetch leaderboard")?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(anyhow!("non-200 status returned when fetching leaderboard: {status}"));
+    }
+    // AoC redirects to the login page rather than failing when the session is not accepted.
+    let body = response.text().context("failed to read leaderboard")?;
+    let leaderboard = Leaderboard::parse(body.as_bytes())
+        .context("the session may have expired or may not have access to this leaderboard")?;
+    cache_all(cache_path, &body, cache.key())?;
+    Ok(leaderboard)
+}
+
+/// Returns whether the cached file was written recently enough that it should not be refreshed.
+fn is_fresh(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .is_ok_and(|modified| modified.elapsed().is_ok_and(|age| age < MIN_REFRESH))
+}
+
+/// Formats a duration as `H:MM:SS`.
+fn format_time(time: Duration) -> String {
+    let secs = time.as_secs();
+    format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+fn year_from_str<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<u16, D::Error> {
+    String::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::{MockServer, TempDir};
+
+    const FIXTURE: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/leaderboard/2024.json"));
+
+    #[test]
+    fn parses_and_ranks_members() {
+        let leaderboard = Leaderboard::parse(FIXTURE.as_bytes()).unwrap();
+        assert_eq!(leaderboard.event, 2024);
+        assert_eq!(leaderboard.latest_day(), Some(2));
+        let names: Vec<_> = leaderboard.ranked().into_iter().map(Member::display_name).collect();
+        assert_eq!(names, ["Alice", "(anonymous user #2)", "Carol"]);
+
+        let alice = &leaderboard.members["1"];
+        let day1 = ChallengeDate { year: 2024, day: 1 };
+        assert_eq!(alice.star_time(&day1, 2), Some(Duration::from_secs(600)));
+        assert_eq!(alice.star_time(&ChallengeDate { year: 2024, day: 2 }, 2), None);
+    }
+
+    #[test]
+    fn refreshes_at_most_every_15_minutes() {
+        let root = TempDir::new("leaderboard");
+        let (cache, account) = (Cache::at(root.path()), Account::default().with_session("abc"));
+        let server = MockServer::start();
+        server.fixture("GET", "/2024/leaderboard/private/view/1.json", "leaderboard/2024.json");
+
+        for _ in 0..2 {
+            let leaderboard = Leaderboard::fetch_from(server.url(), 2024, 1, &account, &cache).unwrap();
+            assert_eq!(leaderboard.members.len(), 3);
+        }
+        assert_eq!(server.requests().len(), 1);
+
+        // Pretend the cached copy is stale.
+        let stale = SystemTime::now() - MIN_REFRESH;
+        fs::File::options()
+            .write(true)
+            .open(cache.leaderboard_path(2024, 1, &account))
+            .unwrap()
+            .set_modified(stale)
+            .unwrap();
+        Leaderboard::fetch_from(server.url(), 2024, 1, &account, &cache).unwrap();
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[test]
+    fn rejects_login_page() {
+        let root = TempDir::new("leaderboard-login");
+        let (cache, account) = (Cache::at(root.path()), Account::default().with_session("abc"));
+        let server = MockServer::start();
+        server.route("GET", "/2024/leaderboard/private/view/1.json", 200, "<!DOCTYPE html><html>Log in</html>");
+        assert!(Leaderboard::fetch_from(server.url(), 2024, 1, &account, &cache).is_err());
+        assert!(!cache.leaderboard_path(2024, 1, &account).exists());
+    }
+
+    #[test]
+    fn renders_table() {
+        let rendered = Leaderboard::parse(FIXTURE.as_bytes()).unwrap().render(1);
+        let lines: Vec<_> = rendered.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "     Score  1234567890123456789012345         1/1        1/2  Name");
+        assert_eq!(lines[1], "  1)    11  *+.......................     0:05:00    0:10:00  Alice");
+        assert_eq!(lines[2], "  2)     5  +........................     0:08:20          -  (anonymous user #2)");
+        assert_eq!(lines[3], "  3)     0  .........................           -          -  Carol");
+    }
+
+    #[test]
+    fn renders_times_for_every_day() {
+        let rendered = Leaderboard::parse(FIXTURE.as_bytes()).unwrap().render_times();
+        let lines: Vec<_> = rendered.lines().collect();
+        assert_eq!(lines, [
+            "Day      Part 1     Part 2  Name",
+            "  1     0:05:00    0:10:00  Alice",
+            "  1     0:08:20          -  (anonymous user #2)",
+            "  2     1:00:00          -  Alice",
+        ]);
+    }
+}
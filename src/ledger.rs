@@ -0,0 +1,246 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::account::Account;
+use crate::cache::Cache;
+use crate::date::ChallengeDate;
+use crate::solutions::Part;
+use crate::submit::Verdict;
+use crate::Answer;
+
+/// A single answer that was submitted to AoC.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entry {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+/// Why a candidate answer was refused without submitting it.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Refusal {
+    AlreadySolved {
+        answer: String,
+    },
+    AlreadySubmitted(Entry),
+    /// The candidate is not below an answer that was too high.
+    NotBelow(Entry),
+    /// The candidate is not above an answer that was too low.
+    NotAbove(Entry),
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AlreadySolved { answer } => write!(f, "already solved, the accepted answer was {answer}"),
+            Self::AlreadySubmitted(entry) => {
+                write!(f, "{} was already submitted {}: {}", entry.answer, ago(entry.timestamp), entry.verdict)
+            },
+            Self::NotBelow(entry) => {
+                write!(f, "must be lower than {}, which was too high ({})", entry.answer, ago(entry.timestamp))
+            },
+            Self::NotAbove(entry) => {
+                write!(f, "must be higher than {}, which was too low ({})", entry.answer, ago(entry.timestamp))
+            },
+        }
+    }
+}
+
+/// Every answer submitted for one part of a day's challenge.
+pub struct Ledger {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl Ledger {
+    /// Load the account's ledger for the given challenge date and part.
+    pub fn load(date: &ChallengeDate, part: Part, account: &Account, cache: &Cache) -> io::Result<Self> {
+        Self::open(cache.ledger_path(date, part, account))
+    }
+
+    fn open(path: PathBuf) -> io::Result<Self> {
+        let entries = match fs::read_to_string(&path) {
+            Ok(contents) => contents.lines().filter_map(parse_entry).collect(),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(error) => return Err(error),
+        };
+        Ok(Self { path, entries })
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Check whether the candidate answer is worth submitting given what has been submitted before.
+    pub fn check(&self, candidate: &Answer) -> Result<(), Refusal> {
+        let candidate = candidate.value().to_string();
+        if let Some(correct) = self.entries.iter().find(|entry| entry.verdict == Verdict::Correct) {
+            return Err(Refusal::AlreadySolved { answer: correct.answer.clone() });
+        }
+        if let Some(entry) = self.entries.iter().find(|entry| entry.answer == candidate && is_checked(&entry.verdict)) {
+            return Err(Refusal::AlreadySubmitted(entry.clone()));
+        }
+
+        let Ok(value) = candidate.parse::<i128>() else {
+            return Ok(());
+        };
+        let bound = |verdict: Verdict| {
+            self.entries
+                .iter()
+                .filter(move |entry| entry.verdict == verdict)
+                .filter_map(|entry| Some((entry.answer.parse::<i128>().ok()?, entry)))
+        };
+        if let Some((high, entry)) = bound(Verdict::TooHigh).min_by_key(|(high, _)| *high) {
+            if value >= high {
+                return Err(Refusal::NotBelow(entry.clone()));
+            }
+        }
+        if let Some((low, entry)) = bound(Verdict::TooLow).max_by_key(|(low, _)| *low) {
+            if value <= low {
+                return Err(Refusal::NotAbove(entry.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Record the verdict for a submitted answer and persist it.
+    pub fn record(&mut self, answer: &Answer, verdict: Verdict) -> io::Result<()> {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs());
+        let entry = Entry { timestamp, verdict, answer: answer.value().to_string() };
+        if let Some(directory) = self.path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", format_entry(&entry))?;
+        self.entries.push(entry);
+        Ok(())
+    }
+}
+
+/// Returns whether AoC actually checked the answer for this verdict.
+fn is_checked(verdict: &Verdict) -> bool {
+    !matches!(verdict, Verdict::RateLimited(_) | Verdict::AlreadySolved)
+}
+
+/// Format an entry as a line of `<timestamp>\t<verdict>\t<answer>`.
+fn format_entry(entry: &Entry) -> String {
+    let verdict = match &entry.verdict {
+        Verdict::Correct => "correct".to_owned(),
+        Verdict::TooHigh => "too-high".to_owned(),
+        Verdict::TooLow => "too-low".to_owned(),
+        Verdict::Wrong => "wrong".to_owned(),
+        Verdict::RateLimited(wait) => format!("rate-limited-{}", wait.as_secs()),
+        Verdict::AlreadySolved => "already-solved".to_owned(),
+    };
+    format!("{}\t{verdict}\t{}", entry.timestamp, escape(&entry.answer))
+}
+
+fn parse_entry(line: &str) -> Option<Entry> {
+    let mut tokens = line.splitn(3, '\t');
+    let timestamp = tokens.next()?.parse().ok()?;
+    let verdict = match tokens.next()? {
+        "correct" => Verdict::Correct,
+        "too-high" => Verdict::TooHigh,
+        "too-low" => Verdict::TooLow,
+        "wrong" => Verdict::Wrong,
+        "already-solved" => Verdict::AlreadySolved,
+        other => Verdict::RateLimited(Duration::from_secs(other.strip_prefix("rate-limited-")?.parse().ok()?)),
+    };
+    let answer = unescape(tokens.next()?)?;
+    Some(Entry { timestamp, verdict, answer })
+}
+
+/// Escape backslashes, tabs and line breaks so the value fits in one TSV field.
+pub(crate) fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Reverse `escape`, returning `None` for an invalid escape sequence.
+pub(crate) fn unescape(field: &str) -> Option<String> {
+    let mut value = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(ch) = chars.next() {
+        value.push(match ch {
+            '\\' => match chars.next()? {
+                '\\' => '\\',
+                't' => '\t',
+                'n' => '\n',
+                'r' => '\r',
+                _ => return None,
+            },
+            ch => ch,
+        });
+    }
+    Some(value)
+}
+
+/// Describe how long ago the timestamp was, e.g. "3h ago".
+fn ago(timestamp: u64) -> String {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs());
+    let elapsed = now.saturating_sub(timestamp);
+    match elapsed {
+        0..60 => format!("{elapsed}s ago"),
+        60..3600 => format!("{}m ago", elapsed / 60),
+        3600..86400 => format!("{}h ago", elapsed / 3600),
+        _ => format!("{}d ago", elapsed / 86400),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn with_entries(entries: &[(Verdict, &str)]) -> Ledger {
+        let entries = entries
+            .iter()
+            .map(|(verdict, answer)| Entry { timestamp: 0, verdict: verdict.clone(), answer: answer.to_string() })
+            .collect();
+        Ledger { path: PathBuf::new(), entries }
+    }
+
+    #[test]
+    fn refuses_repeats_and_out_of_bounds() {
+        let ledger = with_entries(&[(Verdict::TooHigh, "100"), (Verdict::TooLow, "20"), (Verdict::Wrong, "50")]);
+        assert!(matches!(ledger.check(&Answer::from(50_u32)), Err(Refusal::AlreadySubmitted(_))));
+        assert!(matches!(ledger.check(&Answer::from(100_u32)), Err(Refusal::AlreadySubmitted(_))));
+        assert!(matches!(ledger.check(&Answer::from(101_u32)), Err(Refusal::NotBelow(_))));
+        assert!(matches!(ledger.check(&Answer::from(-3_i32)), Err(Refusal::NotAbove(_))));
+        assert_eq!(ledger.check(&Answer::from(21_u32)), Ok(()));
+        assert_eq!(ledger.check(&Answer::from("ABC".to_owned())), Ok(()));
+    }
+
+    #[test]
+    fn ignores_unchecked_and_refuses_once_solved() {
+        let ledger = with_entries(&[(Verdict::RateLimited(Duration::from_secs(30)), "7")]);
+        assert_eq!(ledger.check(&Answer::from(7_u32)), Ok(()));
+        let ledger = with_entries(&[(Verdict::Correct, "7")]);
+        assert!(matches!(ledger.check(&Answer::from(8_u32)), Err(Refusal::AlreadySolved { .. })));
+    }
+
+    #[test]
+    fn round_trips_through_file() {
+        let path = std::env::temp_dir().join(format!("aoc-ledger-{}.tsv", std::process::id()));
+        _ = fs::remove_file(&path);
+        let mut ledger = Ledger::open(path.clone()).unwrap();
+        ledger.record(&Answer::from(10_u32), Verdict::TooLow).unwrap();
+        ledger.record(&Answer::from(10_u32), Verdict::RateLimited(Duration::from_secs(42))).unwrap();
+        ledger.record(&Answer::from("A\tB\nC\\n".to_owned()), Verdict::Wrong).unwrap();
+        let reloaded = Ledger::open(path.clone()).unwrap();
+        assert_eq!(reloaded.entries(), ledger.entries());
+        fs::remove_file(path).unwrap();
+    }
+}
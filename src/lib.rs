@@ -1,11 +1,23 @@
-#![feature(iter_collect_into)]
-#![feature(linked_list_cursors)]
 #![feature(pattern)]
 
+pub mod account;
+pub mod answer;
+pub mod bench;
+pub mod cache;
 pub mod date;
+pub mod description;
+pub mod fetch;
 pub mod input;
+pub mod known;
+pub mod leaderboard;
+pub mod ledger;
+#[cfg(test)]
+mod mock;
 pub mod solutions;
+pub mod stats;
+pub mod submit;
 mod util;
 
-// Re-export for convenient references from solutions (`crate::Input`)
+// Re-export for convenient references from solutions (`crate::Input`, `crate::Answer`)
+pub use answer::Answer;
 pub use input::Input;
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use url::Url;
+
+/// A request the mock server received.
+#[derive(Clone, Debug)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    /// Header names are lowercase.
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(header, _)| header.eq_ignore_ascii_case(name)).map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Default)]
+struct State {
+    /// Responses keyed by method and path. Each request pops the next response, except the last,
+    /// which keeps being served.
+    routes: HashMap<(String, String), Vec<(u16, String)>>,
+    requests: Vec<Request>,
+}
+
+/// A local stand-in for AoC's website, listening on a random port until the tests end, so that
+/// fetching, caching and submitting can be tested end to end without the network.
+pub struct MockServer {
+    url: Url,
+    state: Arc<Mutex<State>>,
+}
+
+impl MockServer {
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        let state = Arc::new(Mutex::new(State::default()));
+        let shared = Arc::clone(&state);
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { continue };
+                let state = Arc::clone(&shared);
+                std::thread::spawn(move || serve(stream, &state));
+            }
+        });
+        Self { url, state }
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Respond to requests for the path with the given status and body. Routing the same path
+    /// again queues another response, to be served once the previous ones have been.
+    pub fn route(&self, method: &str, path: &str, status: u16, body: impl Into<String>) -> &Self {
+        let mut state = self.state.lock().unwrap();
+        state.routes.entry((method.to_owned(), path.to_owned())).or_default().push((status, body.into()));
+        self
+    }
+
+    /// Respond to requests for the path with the contents of a file in `fixtures/`.
+    pub fn fixture(&self, method: &str, path: &str, fixture: impl AsRef<Path>) -> &Self {
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures").join(fixture);
+        self.route(method, path, 200, std::fs::read_to_string(fixture).unwrap())
+    }
+
+    /// Returns every request received so far, in order.
+    pub fn requests(&self) -> Vec<Request> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+/// A directory under the system's temp directory for a test to use as a cache root or config
+/// directory. It is not created up front, and is deleted when dropped, even if the test fails.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// `name` must be unique among the tests, since they run in parallel.
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("aoc-{name}-{}", std::process::id()));
+        _ = std::fs::remove_dir_all(&path);
+        Self(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+fn serve(stream: TcpStream, state: &Mutex<State>) {
+    let mut reader = BufReader::new(stream);
+    let Some(request) = read_request(&mut reader) else {
+        return;
+    };
+    let (status, body) = {
+        let mut state = state.lock().unwrap();
+        let response = match state.routes.get_mut(&(request.method.clone(), request.path.clone())) {
+            Some(responses) if responses.len() > 1 => responses.remove(0),
+            Some(responses) => responses[0].clone(),
+            None => (404, "404 Not Found".to_owned()),
+        };
+        state.requests.push(request);
+        response
+    };
+    let response =
+        format!("HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}", body.len());
+    _ = reader.get_mut().write_all(response.as_bytes());
+}
+
+fn read_request(reader: &mut BufReader<TcpStream>) -> Option<Request> {
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let mut request_line = line.split_whitespace();
+    let (method, path) = (request_line.next()?.to_owned(), request_line.next()?.to_owned());
+
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        match line.trim_end().split_once(':') {
+            Some((name, value)) => headers.push((name.to_ascii_lowercase(), value.trim().to_owned())),
+            None => break,
+        }
+    }
+    let content_length =
+        headers.iter().find(|(name, _)| name == "content-length").map_or(0, |(_, value)| value.parse().unwrap_or(0));
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).ok()?;
+    Some(Request { method, path, headers, body: String::from_utf8_lossy(&body).into_owned() })
+}
@@ -1,3 +1,151 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::date::ChallengeDate;
+use crate::{Answer, Input};
+
 mod macros;
 
-macros::years!(/* year_2022, year_2023, */ year_2024);
+macros::years!(year_2022, year_2023, year_2024);
+
+/// One of the two parts of a day's challenge.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl TryFrom<u8> for Part {
+    type Error = InvalidPart;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::One),
+            2 => Ok(Self::Two),
+            other => Err(InvalidPart(other)),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::One => write!(f, "1"),
+            Self::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// A registered solution for a single day's challenge.
+pub struct Solution {
+    pub date: ChallengeDate,
+    /// Path to the module that implements the solution (e.g. `aoc::solutions::year_2024::day7`).
+    pub module: &'static str,
+    /// Runs the given parts against the input, returning their answers in the same order.
+    run: fn(Input, &[Part]) -> Vec<Answer>,
+    /// Runs the given parts against the input, timing how long they take.
+    time: fn(Input, &[Part]) -> Timing,
+}
+
+/// How long a single run of some parts of a solution took.
+#[derive(Debug)]
+pub struct Timing {
+    /// Time spent parsing the input, for days that parse it separately from solving.
+    pub parse: Option<Duration>,
+    /// Time spent solving each part, or all of them at once for days that solve them together.
+    pub solve: Vec<(Vec<Part>, Duration)>,
+    /// The answers to the parts, in the order they were given.
+    pub answers: Vec<Answer>,
+}
+
+impl Solution {
+    /// Run the given part of this solution against the input.
+    pub fn run(&self, part: Part, input: Input) -> Answer {
+        (self.run)(input, &[part]).pop().unwrap()
+    }
+
+    /// Run both parts of this solution against the input, which is only read (and parsed, for
+    /// days that support it) once.
+    pub fn run_both(&self, input: Input) -> [Answer; 2] {
+        let mut answers = (self.run)(input, &Part::ALL).into_iter();
+        [answers.next().unwrap(), answers.next().unwrap()]
+    }
+
+    /// Run the given parts of this solution against the input, timing them.
+    pub fn time(&self, parts: &[Part], input: Input) -> Timing {
+        (self.time)(input, parts)
+    }
+}
+
+/// Returns every implemented solution, ordered by date.
+pub fn all() -> impl Iterator<Item = &'static Solution> {
+    YEARS.iter().flat_map(|solutions| solutions.iter())
+}
+
+/// Look up the solution for the given challenge date.
+pub fn find(date: ChallengeDate) -> Result<&'static Solution, NotImplemented> {
+    all().find(|solution| solution.date == date).ok_or(NotImplemented(date))
+}
+
+/// Returned when looking up a challenge date that has no solution.
+#[derive(Debug)]
+pub struct NotImplemented(pub ChallengeDate);
+
+impl fmt::Display for NotImplemented {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} has not been implemented", self.0)
+    }
+}
+
+impl std::error::Error for NotImplemented {}
+
+/// Returned when a part number other than 1 or 2 is given.
+#[derive(Debug)]
+pub struct InvalidPart(pub u8);
+
+impl fmt::Display for InvalidPart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid part number {}, expected 1 or 2", self.0)
+    }
+}
+
+impl std::error::Error for InvalidPart {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn registry_is_ordered_and_unique() {
+        let dates: Vec<_> = all().map(|solution| (solution.date.year, solution.date.day)).collect();
+        assert!(dates.windows(2).all(|pair| pair[0] < pair[1]), "solutions are out of order: {dates:?}");
+    }
+
+    #[test]
+    fn runs_both_parts_from_one_input() {
+        let input = "3   4\n4   3\n2   5\n1   3\n";
+        let solution = find(ChallengeDate { year: 2024, day: 1 }).unwrap();
+        assert_eq!(solution.run_both(Input::memory(input)), [Answer::from(5_i32), Answer::from(10_u32)]);
+        assert_eq!(solution.run(Part::Two, Input::memory(input)), Answer::from(10_u32));
+
+        let input = "AAAA\nBBCD\nBBCC\nEEEC\n";
+        let solution = find(ChallengeDate { year: 2024, day: 12 }).unwrap();
+        assert_eq!(solution.run_both(Input::memory(input)), [Answer::from(140_usize), Answer::from(80_usize)]);
+
+        let input = "7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9\n";
+        let solution = find(ChallengeDate { year: 2024, day: 2 }).unwrap();
+        assert_eq!(solution.run_both(Input::memory(input)), [Answer::from(2_usize), Answer::from(4_usize)]);
+        assert_eq!(solution.run(Part::One, Input::memory(input)), Answer::from(2_usize));
+    }
+
+    #[test]
+    fn find_by_date() {
+        let solution = find(ChallengeDate { year: 2024, day: 12 }).unwrap();
+        assert_eq!(solution.module, "aoc::solutions::year_2024::day12");
+        assert!(find(ChallengeDate { year: 2015, day: 1 }).is_err());
+    }
+}
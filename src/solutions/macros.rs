@@ -1,59 +1,208 @@
-/// Generate module definitions for each day's solution and define `run` to
-/// dispatch to the right one.
+/// Generate module definitions for each day's solution and register them in
+/// `SOLUTIONS`.
+///
+/// By default a day's module exposes `part1(Input)` and `part2(Input)`. A day can instead be
+/// marked as:
+/// - `dayN: parse`, exposing `parse(Input) -> T` with `part1(&T)` and `part2(&T)`.
+/// - `dayN: solve`, exposing `solve(Input) -> (A, B)` to compute both parts at once.
 ///
 /// Usage:
-/// ```rust
-/// days!(day1, day2, day3, ...);
+/// ```ignore
+/// days!(2024, day1: parse, day2, day3: solve, ...);
 /// ```
 macro_rules! days {
-    ($year:literal, $($day:ident), *) => {
+    ($year:literal, $($day:ident $(: $kind:ident)?), *) => {
         $( mod $day; )*
 
-        pub fn run(day: &str, part: u8) {
-            match day {
-                $(
-                    day @ stringify!($day) => {
-                        // NOTE: u8 is fine since this is only every for the days until Christmas :^)
-                        let day_number: u8 = day.strip_prefix("day")
-                            .expect("identifier was not prefixed with \"day\"")
-                            .parse()
-                            .expect("identifier was not in the proper format \"day<x>\"");
-
-                        // TODO: Allow user to input which year to run.
-                        let input = crate::input::Input::fetch(crate::date::ChallengeDate { year: $year, day: day_number });
-                        match part {
-                            1 => println!("Part #1: {}", $day::part1(input)),
-                            2 => println!("Part #2: {}", $day::part2(input)),
-                            _ => panic!("Invalid part number"),
-                        };
+        pub(super) const SOLUTIONS: &[crate::solutions::Solution] = &[
+            $(
+                crate::solutions::Solution {
+                    date: crate::date::ChallengeDate {
+                        year: $year,
+                        day: crate::solutions::macros::parse_day(stringify!($day)),
                     },
-                )*
-                other => panic!("{} has not been implemented", other),
+                    module: concat!(module_path!(), "::", stringify!($day)),
+                    run: crate::solutions::macros::runner!($day $(: $kind)?),
+                    time: crate::solutions::macros::timer!($day $(: $kind)?),
+                },
+            )*
+        ];
+    };
+}
+
+/// Generate the function that runs the given parts of a day's solution, for `days!`.
+macro_rules! runner {
+    ($day:ident) => {
+        |input, parts| match parts {
+            [crate::solutions::Part::One] => vec![$day::part1(input).into()],
+            [crate::solutions::Part::Two] => vec![$day::part2(input).into()],
+            // Each part consumes the input, so read it once and give each part a copy.
+            parts => {
+                let data = input.read_all();
+                parts
+                    .iter()
+                    .map(|part| match part {
+                        crate::solutions::Part::One => $day::part1(crate::Input::memory(data.clone())).into(),
+                        crate::solutions::Part::Two => $day::part2(crate::Input::memory(data.clone())).into(),
+                    })
+                    .collect()
+            },
+        }
+    };
+    ($day:ident : parse) => {
+        |input, parts| {
+            let parsed = $day::parse(input);
+            parts
+                .iter()
+                .map(|part| match part {
+                    crate::solutions::Part::One => $day::part1(&parsed).into(),
+                    crate::solutions::Part::Two => $day::part2(&parsed).into(),
+                })
+                .collect()
+        }
+    };
+    ($day:ident : solve) => {
+        |input, parts| {
+            let (part1, part2) = $day::solve(input);
+            let answers: [crate::Answer; 2] = [part1.into(), part2.into()];
+            parts.iter().map(|&part| answers[part as usize].clone()).collect()
+        }
+    };
+}
+
+/// Generate the function that times the given parts of a day's solution, for `days!`.
+///
+/// Parsing is only timed separately for `dayN: parse` days, other days include it in the solve
+/// time. `dayN: solve` days solve both parts at once, so they are timed once for all the parts.
+macro_rules! timer {
+    ($day:ident) => {
+        |input, parts| {
+            // Each part consumes the input, so read it up front and give each part a copy.
+            let data = input.read_all();
+            let mut timing = crate::solutions::Timing { parse: None, solve: Vec::new(), answers: Vec::new() };
+            for &part in parts {
+                let input = crate::Input::memory(data.clone());
+                let started = std::time::Instant::now();
+                let answer = match part {
+                    crate::solutions::Part::One => $day::part1(input).into(),
+                    crate::solutions::Part::Two => $day::part2(input).into(),
+                };
+                timing.solve.push((vec![part], started.elapsed()));
+                timing.answers.push(answer);
+            }
+            timing
+        }
+    };
+    ($day:ident : parse) => {
+        |input, parts| {
+            let started = std::time::Instant::now();
+            let parsed = $day::parse(input);
+            let parse = started.elapsed();
+            let mut timing = crate::solutions::Timing { parse: Some(parse), solve: Vec::new(), answers: Vec::new() };
+            for &part in parts {
+                let started = std::time::Instant::now();
+                let answer = match part {
+                    crate::solutions::Part::One => $day::part1(&parsed).into(),
+                    crate::solutions::Part::Two => $day::part2(&parsed).into(),
+                };
+                timing.solve.push((vec![part], started.elapsed()));
+                timing.answers.push(answer);
+            }
+            timing
+        }
+    };
+    ($day:ident : solve) => {
+        |input, parts| {
+            let started = std::time::Instant::now();
+            let (part1, part2) = $day::solve(input);
+            let solve = started.elapsed();
+            let answers: [crate::Answer; 2] = [part1.into(), part2.into()];
+            crate::solutions::Timing {
+                parse: None,
+                solve: vec![(parts.to_vec(), solve)],
+                answers: parts.iter().map(|&part| answers[part as usize].clone()).collect(),
             }
         }
     };
 }
 
-/// Generate module definitions for each year and define `run` to dispatch to
-/// the right one.
+/// Generate module definitions for each year and collect their solutions into
+/// `YEARS`.
 ///
 /// Usage:
-/// ```rust
+/// ```ignore
 /// years!(year_2022, year_2023, ...);
 /// ```
 macro_rules! years {
     ($($year:ident), *) => {
         $( mod $year; )*
 
-        pub fn run(year: &str, day: &str, part: u8) {
-            match year {
-                $(
-                    stringify!($year) => $year::run(day, part),
-                )*
-                other => panic!("{} has not been implemented", other),
-            }
+        const YEARS: &[&[Solution]] = &[$( $year::SOLUTIONS ),*];
+    }
+}
+
+/// Generate a `#[test]` for each listed part of a day's solution, checking it against the example
+/// fixtures in `fixtures/<year>/day<day>/part<n>.{input,answer}` (see `cargo run -- examples`).
+///
+/// The parts are run through the registered `Solution`, so this works for `: parse` and `: solve`
+/// days too.
+///
+/// Usage:
+/// ```ignore
+/// examples!(2024, 8, part1, part2);
+/// ```
+macro_rules! examples {
+    ($year:literal, $day:literal, $($part:ident), *) => {
+        #[cfg(test)]
+        mod examples {
+            $(
+                #[test]
+                fn $part() {
+                    const FIXTURE: &str =
+                        concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/", $year, "/day", $day, "/", stringify!($part));
+                    let input = crate::Input::memory(include_str!(concat!(
+                        env!("CARGO_MANIFEST_DIR"), "/fixtures/", $year, "/day", $day, "/", stringify!($part), ".input"
+                    )));
+                    let expected = include_str!(concat!(
+                        env!("CARGO_MANIFEST_DIR"), "/fixtures/", $year, "/day", $day, "/", stringify!($part), ".answer"
+                    ));
+                    let part = match stringify!($part) {
+                        "part1" => crate::solutions::Part::One,
+                        "part2" => crate::solutions::Part::Two,
+                        other => panic!("{other} is not a part, expected part1 or part2"),
+                    };
+                    let date = crate::date::ChallengeDate { year: $year, day: $day };
+                    let answer = crate::solutions::find(date).unwrap().run(part, input);
+                    assert_eq!(answer.value().to_string(), expected.trim(), "example from {FIXTURE}");
+                }
+            )*
         }
+    };
+}
+
+/// Parses the day number out of a `day<x>` module identifier.
+///
+/// This is evaluated at compile time, so a badly named module fails the build.
+pub(crate) const fn parse_day(ident: &str) -> u8 {
+    let bytes = ident.as_bytes();
+    assert!(
+        bytes.len() > 3 && bytes[0] == b'd' && bytes[1] == b'a' && bytes[2] == b'y',
+        "identifier was not prefixed with \"day\""
+    );
+
+    // NOTE: u8 is fine since this is only ever for the days until Christmas :^)
+    let mut day: u8 = 0;
+    let mut idx = 3;
+    while idx < bytes.len() {
+        assert!(bytes[idx].is_ascii_digit(), "identifier was not in the proper format \"day<x>\"");
+        day = day * 10 + (bytes[idx] - b'0');
+        idx += 1;
     }
+    day
 }
 
-pub(crate) use {days, years};
+pub(crate) use days;
+pub(crate) use examples;
+pub(crate) use runner;
+pub(crate) use timer;
+pub(crate) use years;
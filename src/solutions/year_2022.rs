@@ -1,3 +1,3 @@
 use crate::solutions::macros::days;
 
-days!(2022, day1, day2, day3, day4, day5, day6);
+days!(2022, day1, day2: solve, day3, day4: solve, day5: solve, day6);
@@ -1,20 +1,17 @@
 use std::collections::BinaryHeap;
 
-pub fn solve(input: crate::Input) {
+/// Returns the total calories carried by each elf, largest first.
+fn calories(input: crate::Input) -> BinaryHeap<u32> {
     let input = input.read_all();
     let blocks = input.split("\n\n").map(str::trim);
-    let mut heap: BinaryHeap<u32> = blocks
-        .map(|block| {
-            block
-                .split("\n")
-                .map(|line| line.parse::<u32>().unwrap())
-                .sum()
-        })
-        .collect();
+    blocks.map(|block| block.split('\n').map(|line| line.parse::<u32>().unwrap()).sum()).collect()
+}
 
-    let top = heap.pop().unwrap();
-    println!("Answer #1: {}", top);
+pub fn part1(input: crate::Input) -> u32 {
+    calories(input).pop().unwrap()
+}
 
-    let top3 = top + heap.pop().unwrap() + heap.pop().unwrap();
-    println!("Answer #2: {}", top3);
+pub fn part2(input: crate::Input) -> u32 {
+    let mut heap = calories(input);
+    heap.pop().unwrap() + heap.pop().unwrap() + heap.pop().unwrap()
 }
@@ -85,12 +85,11 @@ impl FromStr for RpsOutcome {
     }
 }
 
-pub fn solve(input: crate::Input) {
-    let input = input.read_all();
-    let (total_score_winning, total_score_desired_outcome): (u32, u32) = input
-        .lines()
+pub fn solve(input: crate::Input) -> (u32, u32) {
+    input
+        .read_lines()
         .map(|line| {
-            let tokens: Vec<_> = line.split(" ").collect();
+            let tokens: Vec<_> = line.split(' ').collect();
             let move_theirs: RpsMove = tokens[0].parse().unwrap();
 
             // Calculate the score when the second column is the winning move (Part 1).
@@ -104,8 +103,5 @@ pub fn solve(input: crate::Input) {
             (score_winning, score_desired_outcome)
         })
         .reduce(|a, b| (a.0 + b.0, a.1 + b.1))
-        .unwrap();
-
-    println!("Answer #1: {}", total_score_winning);
-    println!("Answer #2: {}", total_score_desired_outcome);
+        .unwrap()
 }
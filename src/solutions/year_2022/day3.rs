@@ -5,22 +5,19 @@ fn priority(c: char) -> u32 {
     const LOWERCASE_OFFSET: u32 = 96;
     const ALPHABET_LENGTH: u32 = 26;
 
-    if c >= 'A' && c <= 'Z' {
+    if c.is_ascii_uppercase() {
         return c as u32 - UPPERCASE_OFFSET + ALPHABET_LENGTH;
     }
-    if c >= 'a' && c <= 'z' {
+    if c.is_ascii_lowercase() {
         return c as u32 - LOWERCASE_OFFSET;
     }
 
     panic!("We only know the priority of alphabetical characters");
 }
 
-pub fn solve(input: crate::Input) {
-    let input = input.read_all();
-    let lines: Vec<_> = input.lines().collect();
-
-    let sum_of_priorities_p1: u32 = lines
-        .iter()
+pub fn part1(input: crate::Input) -> u32 {
+    input
+        .read_lines()
         .map(|line| {
             let chars: Vec<_> = line.chars().collect();
             let halves: Vec<_> = chars.chunks(chars.len() / 2).collect();
@@ -29,15 +26,17 @@ pub fn solve(input: crate::Input) {
             let item_in_both = *first_half.intersection(&second_half).next().unwrap();
             priority(item_in_both)
         })
-        .sum();
+        .sum()
+}
 
-    let sum_of_priorities_p2: u32 = lines
+pub fn part2(input: crate::Input) -> u32 {
+    let lines: Vec<_> = input.read_lines().collect();
+    lines
         .chunks(3)
         .map(|line_group| {
             let char_sets: Vec<_> = line_group
                 .iter()
-                .map(|s| *s)
-                .map(str::chars)
+                .map(|s| s.chars())
                 .map(HashSet::<char>::from_iter)
                 .collect();
 
@@ -53,8 +52,5 @@ pub fn solve(input: crate::Input) {
 
             priority(char_in_all)
         })
-        .sum();
-
-    println!("Answer #1: {}", sum_of_priorities_p1);
-    println!("Answer #2: {}", sum_of_priorities_p2);
+        .sum()
 }
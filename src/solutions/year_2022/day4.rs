@@ -1,11 +1,10 @@
 use std::ops::RangeInclusive;
 
-pub fn solve(input: crate::Input) {
-    let input = input.read_all();
+pub fn solve(input: crate::Input) -> (u32, u32) {
     let mut answer1 = 0;
     let mut answer2 = 0;
 
-    for line in input.lines() {
+    for line in input.read_lines() {
         let (left, right) = parse_line(&line);
         let overlap = Overlap::compute(&left, &right);
         if overlap >= Overlap::Partial {
@@ -16,17 +15,16 @@ pub fn solve(input: crate::Input) {
         }
     }
 
-    println!("Answer #1: {answer1}");
-    println!("Answer #2: {answer2}");
+    (answer1, answer2)
 }
 
 fn parse_line(line: &str) -> (RangeInclusive<u32>, RangeInclusive<u32>) {
-    let (left, right) = line.split_once(",").unwrap();
+    let (left, right) = line.split_once(',').unwrap();
     (parse_range(left), parse_range(right))
 }
 
 fn parse_range(text: &str) -> RangeInclusive<u32> {
-    let (left, right) = text.split_once("-").unwrap();
+    let (left, right) = text.split_once('-').unwrap();
     let left: u32 = left.parse().unwrap();
     let right: u32 = right.parse().unwrap();
     left..=right
@@ -56,8 +54,5 @@ impl Overlap {
 ///
 /// The minimum return value for this function is 0, and the maximum is 2.
 fn count_in_range(left: &RangeInclusive<u32>, right: &RangeInclusive<u32>) -> u8 {
-    [right.contains(left.start()), right.contains(left.end())]
-        .into_iter()
-        .filter(|b| *b)
-        .count() as u8
+    [right.contains(left.start()), right.contains(left.end())].into_iter().filter(|b| *b).count() as u8
 }
@@ -1,6 +1,6 @@
 use itertools::Itertools;
 
-pub fn solve(input: crate::Input) {
+pub fn solve(input: crate::Input) -> (String, String) {
     let input = input.read_all();
     let blocks: Vec<_> = input.split("\n\n").collect();
     let mut stacks1: Stacks = Stacks::parse_block(blocks[0]);
@@ -10,10 +10,7 @@ pub fn solve(input: crate::Input) {
         stacks1.execute_command(&command, false);
         stacks2.execute_command(&command, true);
     }
-    let answer1: String = stacks1.peek_all().collect();
-    let answer2: String = stacks2.peek_all().collect();
-    println!("Answer #1: {answer1}");
-    println!("Answer #2: {answer2}");
+    (stacks1.peek_all().collect(), stacks2.peek_all().collect())
 }
 
 #[derive(Clone)]
@@ -23,9 +20,7 @@ struct Stacks {
 
 impl Stacks {
     fn new(count: usize) -> Self {
-        Self {
-            inner: (0..count).into_iter().map(|_| Vec::new()).collect(),
-        }
+        Self { inner: (0..count).map(|_| Vec::new()).collect() }
     }
 
     fn parse_block(s: &str) -> Self {
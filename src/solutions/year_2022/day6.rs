@@ -1,11 +1,11 @@
 use std::collections::HashSet;
 
-pub fn solve(input: crate::Input) {
-    let input = input.read_all();
-    let start_of_packet = find_first_marker(&input, 4).unwrap();
-    let start_of_message = find_first_marker(&input, 14).unwrap();
-    println!("Answer #1: {}", start_of_packet);
-    println!("Answer #2: {}", start_of_message);
+pub fn part1(input: crate::Input) -> usize {
+    find_first_marker(&input.read_all(), 4).unwrap()
+}
+
+pub fn part2(input: crate::Input) -> usize {
+    find_first_marker(&input.read_all(), 14).unwrap()
 }
 
 fn find_first_marker(input: &str, count: usize) -> Option<usize> {
@@ -1,3 +1,3 @@
 use crate::solutions::macros::days;
 
-days!(2023, day1, day2, day3, day4, day5, day6, day7, day8);
+days!(2023, day1: solve, day2, day3: solve, day4: solve, day5, day6, day7, day8);
@@ -19,7 +19,7 @@ lazy_static! {
     };
 }
 
-pub fn solve(input: crate::Input) {
+pub fn solve(input: crate::Input) -> (u32, u32) {
     let mut all_numbers = Vec::new();
     let mut all_numbers_and_spelled = Vec::new();
 
@@ -49,10 +49,7 @@ pub fn solve(input: crate::Input) {
         all_numbers_and_spelled.push(compose_digits(numbers_and_spelled));
     }
 
-    let sum_numbers: u32 = all_numbers.iter().sum();
-    let sum_numbers_and_spelled: u32 = all_numbers_and_spelled.iter().sum();
-    println!("Answer #1: {sum_numbers}");
-    println!("Answer #2: {sum_numbers_and_spelled}");
+    (all_numbers.iter().sum(), all_numbers_and_spelled.iter().sum())
 }
 
 /// Takes the digits and returns the concatenation of the first and last one.
@@ -103,15 +103,14 @@ impl CubeCounts {
     }
 }
 
-pub fn solve(input: crate::Input) {
-    let games: Vec<_> = input.read_lines().map(|s| Game::parse(&s)).collect();
-    let answer1: u32 = games
-        .iter()
-        .filter(|game| !game.has_impossible_pull())
-        .map(|game| game.id)
-        .sum();
-    let answer2: u32 = games.iter().map(Game::fewest_possible_product).sum();
-
-    println!("Answer #1: {answer1}");
-    println!("Answer #2: {answer2}");
+fn parse(input: crate::Input) -> Vec<Game> {
+    input.read_lines().map(|s| Game::parse(&s)).collect()
+}
+
+pub fn part1(input: crate::Input) -> u32 {
+    parse(input).iter().filter(|game| !game.has_impossible_pull()).map(|game| game.id).sum()
+}
+
+pub fn part2(input: crate::Input) -> u32 {
+    parse(input).iter().map(Game::fewest_possible_product).sum()
 }
@@ -3,6 +3,8 @@ use std::collections::HashSet;
 use lazy_static::lazy_static;
 use maplit::hashset;
 
+use crate::util::grid::Grid;
+
 lazy_static! {
     /// The set of symbols that indicate adjacent part numbers.
     static ref SYMBOLS: HashSet<char> = hashset! {
@@ -13,7 +15,7 @@ lazy_static! {
 
 /// A 2D engine schematic, parsed from the challenge input.
 struct Schematic {
-    table: Vec<Vec<Cell>>,
+    table: Grid<Value>,
     part_numbers: Vec<PartNumber>,
 }
 
@@ -26,30 +28,13 @@ impl Schematic {
             let mut table_row = Vec::new();
             for (col, ch) in line.chars().enumerate() {
                 let location = Location::new(row, col);
-                let cell = parser.process(ch, location);
-                table_row.push(cell);
+                table_row.push(parser.process(ch, location));
             }
             parser.pop_buffer();
             table.push(table_row);
         }
 
-        Self {
-            table,
-            part_numbers: parser.part_numbers,
-        }
-    }
-
-    fn rows(&self) -> usize {
-        self.table.len()
-    }
-
-    fn cols(&self) -> usize {
-        self.table[0].len()
-    }
-
-    /// If a digit exists at the given location, returns it.
-    fn digit_at(&self, location: &Location) -> Option<Digit> {
-        self.table[location.row][location.col].value.as_digit()
+        Self { table: Grid::new(table), part_numbers: parser.part_numbers }
     }
 
     /// Performs an inspection of the engine schematic, yielding pertinent information such as the
@@ -58,17 +43,10 @@ impl Schematic {
         let mut part_numbers = HashSet::new();
         let mut gear_ratios = Vec::new();
 
-        let symbols: Vec<_> = self
-            .table
-            .iter()
-            .flatten()
-            .filter(|cell| matches!(cell.value, Value::Symbol))
-            .collect();
-
-        for symbol in symbols {
+        for symbol in self.table.find_all(|value| matches!(value, Value::Symbol)) {
             let mut adjacent_part_numbers = HashSet::new();
-            for adjacency in symbol.location.adjacent(self) {
-                if let Some(digit) = self.digit_at(&adjacency) {
+            for adjacency in symbol.adjacent8() {
+                if let Some(digit) = adjacency.data.value.as_digit() {
                     adjacent_part_numbers.insert(&self.part_numbers[digit.part_number_idx]);
                 }
             }
@@ -77,12 +55,7 @@ impl Schematic {
             // As part of the inspection, we need to collect all the gear *ratios*, which is the
             // product of the two adjacent part numbers.
             if adjacent_part_numbers.len() == 2 {
-                gear_ratios.push(
-                    adjacent_part_numbers
-                        .iter()
-                        .map(|part_number| part_number.value)
-                        .product(),
-                );
+                gear_ratios.push(adjacent_part_numbers.iter().map(|part_number| part_number.value).product());
             }
 
             part_numbers.extend(adjacent_part_numbers);
@@ -90,10 +63,7 @@ impl Schematic {
 
         Inspection {
             gear_ratios,
-            part_numbers: part_numbers
-                .into_iter()
-                .map(|part_number| part_number.value)
-                .collect(),
+            part_numbers: part_numbers.into_iter().map(|part_number| part_number.value).collect(),
         }
     }
 }
@@ -106,12 +76,12 @@ struct Parser {
 }
 
 impl Parser {
-    fn process(&mut self, ch: char, location: Location) -> Cell {
-        let value = if let Some(digit) = ch.to_digit(10) {
+    fn process(&mut self, ch: char, location: Location) -> Value {
+        if let Some(digit) = ch.to_digit(10) {
             if let Some(curr) = self.buffer.as_mut() {
                 curr.value = curr.value * 10 + digit;
             } else {
-                self.buffer = Some(PartNumber::new(digit, location.clone()));
+                self.buffer = Some(PartNumber::new(digit, location));
             };
 
             // This assumes that our code will later call Parser::pop_buffer and the part number
@@ -126,9 +96,7 @@ impl Parser {
             } else {
                 panic!("invalid character found in input: {ch}");
             }
-        };
-
-        Cell::new(value, location)
+        }
     }
 
     /// If there is currently a part number in the buffer, pop it and push it to the set of part
@@ -140,17 +108,6 @@ impl Parser {
     }
 }
 
-struct Cell {
-    value: Value,
-    location: Location,
-}
-
-impl Cell {
-    fn new(value: Value, location: Location) -> Self {
-        Self { value, location }
-    }
-}
-
 enum Value {
     Digit(Digit),
     Symbol,
@@ -180,10 +137,7 @@ struct Digit {
 
 impl Digit {
     fn new(value: u32, part_number_idx: usize) -> Self {
-        Self {
-            value,
-            part_number_idx,
-        }
+        Self { value, part_number_idx }
     }
 }
 
@@ -209,35 +163,6 @@ impl Location {
     fn new(row: usize, col: usize) -> Self {
         Self { row, col }
     }
-
-    /// Returns all the *valid* `Location`s that are adjacent to this location.
-    fn adjacent(&self, schematic: &Schematic) -> Vec<Self> {
-        [
-            self.with_offset(schematic, -1, -1),
-            self.with_offset(schematic, -1, 0),
-            self.with_offset(schematic, -1, 1),
-            self.with_offset(schematic, 0, -1),
-            self.with_offset(schematic, 0, 1),
-            self.with_offset(schematic, 1, -1),
-            self.with_offset(schematic, 1, 0),
-            self.with_offset(schematic, 1, 1),
-        ]
-        .into_iter()
-        .flatten()
-        .collect()
-    }
-
-    /// Returns a new `Location` instance with the given offsets applied.
-    ///
-    /// If the new location would be out of bounds, returns None.
-    fn with_offset(&self, schematic: &Schematic, row: i32, col: i32) -> Option<Self> {
-        let row = usize::try_from(self.row as i32 - row).ok()?;
-        let col = usize::try_from(self.col as i32 - col).ok()?;
-        if row >= schematic.rows() || col >= schematic.cols() {
-            return None;
-        }
-        Some(Self { row, col })
-    }
 }
 
 struct Inspection {
@@ -248,17 +173,11 @@ struct Inspection {
 fn run<'a>(lines: impl Iterator<Item = &'a str>) -> (u32, u32) {
     let schematic = Schematic::parse(lines);
     let inspection = schematic.inspect();
-    (
-        inspection.part_numbers.iter().sum(),
-        inspection.gear_ratios.iter().sum(),
-    )
+    (inspection.part_numbers.iter().sum(), inspection.gear_ratios.iter().sum())
 }
 
-pub fn solve(input: crate::Input) {
-    let input = input.read_all();
-    let (answer1, answer2) = run(input.lines());
-    println!("Answer #1: {answer1}");
-    println!("Answer #2: {answer2}");
+pub fn solve(input: crate::Input) -> (u32, u32) {
+    run(input.read_all().lines())
 }
 
 #[cfg(test)]
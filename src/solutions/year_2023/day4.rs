@@ -9,7 +9,7 @@ struct Card {
 impl Card {
     fn parse(line: &str) -> Self {
         // Strip off the leading "Card X:" text, we don't need it.
-        let both_token = line.split(':').last().unwrap().trim();
+        let both_token = line.split(':').next_back().unwrap().trim();
         let tokens: Vec<_> = both_token.split('|').collect();
         let winning = HashSet::from_iter(parse_numbers(tokens[0]));
         let you_have = parse_numbers(tokens[1]);
@@ -17,10 +17,7 @@ impl Card {
     }
 
     fn count_matching(&self) -> usize {
-        self.you_have
-            .iter()
-            .filter(|card| self.winning.contains(card))
-            .count()
+        self.you_have.iter().filter(|card| self.winning.contains(card)).count()
     }
 
     fn score(&self) -> u32 {
@@ -33,16 +30,10 @@ impl Card {
 }
 
 fn parse_numbers(text: &str) -> Vec<u32> {
-    text.split_whitespace()
-        .map(|token| token.parse().unwrap())
-        .collect()
+    text.split_whitespace().map(|token| token.parse().unwrap()).collect()
 }
 
-fn count_with_2_rules(
-    cards: &[Card],
-    won: Range<usize>,
-    cache: &mut HashMap<Range<usize>, u32>,
-) -> u32 {
+fn count_with_2_rules(cards: &[Card], won: Range<usize>, cache: &mut HashMap<Range<usize>, u32>) -> u32 {
     if let Some(sum) = cache.get(&won) {
         return *sum;
     }
@@ -51,7 +42,7 @@ fn count_with_2_rules(
     for idx in won.clone() {
         let score = cards[idx].count_matching();
         let lo = idx + 1;
-        let hi = (lo + score as usize).min(cards.len());
+        let hi = (lo + score).min(cards.len());
         sum += count_with_2_rules(cards, lo..hi, cache) + 1;
     }
 
@@ -66,11 +57,8 @@ fn run<'a>(lines: impl Iterator<Item = &'a str>) -> (u32, u32) {
     (answer1, answer2)
 }
 
-pub fn solve(input: crate::Input) {
-    let input = input.read_all();
-    let (answer1, answer2) = run(input.lines());
-    println!("Answer #1: {answer1}");
-    println!("Answer #2: {answer2}");
+pub fn solve(input: crate::Input) -> (u32, u32) {
+    run(input.read_all().lines())
 }
 
 #[cfg(test)]
@@ -33,7 +33,7 @@ impl Conversion {
     }
 
     fn map_range(&self, input: &Range<u64>) -> Overlap {
-        let overlap = Overlap::relative(&self.source, &input);
+        let overlap = Overlap::relative(&self.source, input);
         let inner = overlap.inner.map(|inner| {
             let base = self.destination.start + inner.start;
             base..base + inner.count() as u64
@@ -95,7 +95,7 @@ impl Overlap {
     }
 }
 
-fn parse_blocks<'a>(input: &'a str) -> impl Iterator<Item = &'a str> {
+fn parse_blocks(input: &str) -> impl Iterator<Item = &str> {
     let block_header_regex = Regex::new(".*:\n?").unwrap();
     input
         .split("\n\n")
@@ -182,12 +182,12 @@ fn solve_part2(input: String) -> u64 {
     state[0]
 }
 
-pub fn solve(input: crate::Input) {
-    let input = input.read_all();
-    let answer1 = solve_part1(input.clone());
-    let answer2 = solve_part2(input);
-    println!("Answer #1: {answer1}");
-    println!("Answer #2: {answer2}");
+pub fn part1(input: crate::Input) -> u64 {
+    solve_part1(input.read_all())
+}
+
+pub fn part2(input: crate::Input) -> u64 {
+    solve_part2(input.read_all())
 }
 
 #[cfg(test)]
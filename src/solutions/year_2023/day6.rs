@@ -6,7 +6,7 @@ struct Race {
 }
 
 impl Race {
-    fn parse_as_multiple<'a>(input: String) -> Vec<Self> {
+    fn parse_as_multiple(input: String) -> Vec<Self> {
         let mut parsed = input.lines().map(parse_line_as_multiple);
         let times = parsed.next().unwrap();
         let distances = parsed.next().unwrap();
@@ -58,12 +58,12 @@ fn solve_part2(input: String) -> u64 {
     Race::parse_as_one(input).ways_to_beat_record()
 }
 
-pub fn solve(input: crate::Input) {
-    let input = input.read_all();
-    let answer1 = solve_part1(input.clone());
-    let answer2 = solve_part2(input);
-    println!("Answer #1: {answer1}");
-    println!("Answer #2: {answer2}");
+pub fn part1(input: crate::Input) -> u64 {
+    solve_part1(input.read_all())
+}
+
+pub fn part2(input: crate::Input) -> u64 {
+    solve_part2(input.read_all())
 }
 
 #[cfg(test)]
@@ -54,14 +54,14 @@ impl Hand {
         counts.sort();
         counts.reverse();
 
-        match counts.as_slice() {
-            &[5] => HandClass::FiveOfAKind,
-            &[4, ..] => HandClass::FourOfAKind,
-            &[3, 2] => HandClass::FullHouse,
-            &[3, ..] => HandClass::ThreeOfAKind,
-            &[2, 2, ..] => HandClass::TwoPair,
-            &[2, ..] => HandClass::OnePair,
-            &[..] => HandClass::HighCard,
+        match *counts.as_slice() {
+            [5] => HandClass::FiveOfAKind,
+            [4, ..] => HandClass::FourOfAKind,
+            [3, 2] => HandClass::FullHouse,
+            [3, ..] => HandClass::ThreeOfAKind,
+            [2, 2, ..] => HandClass::TwoPair,
+            [2, ..] => HandClass::OnePair,
+            [..] => HandClass::HighCard,
         }
         .upgrade(jacks)
     }
@@ -153,12 +153,12 @@ fn run(input: String, parse_line: impl Fn(&str) -> HandWager) -> u32 {
         .sum()
 }
 
-pub fn solve(input: crate::Input) {
-    let input = input.read_all();
-    let answer1 = run(input.clone(), |line| HandWager::parse(line, Card::Jack));
-    let answer2 = run(input.clone(), |line| HandWager::parse(line, Card::Joker));
-    println!("Answer #1: {answer1}");
-    println!("Answer #2: {answer2}");
+pub fn part1(input: crate::Input) -> u32 {
+    run(input.read_all(), |line| HandWager::parse(line, Card::Jack))
+}
+
+pub fn part2(input: crate::Input) -> u32 {
+    run(input.read_all(), |line| HandWager::parse(line, Card::Joker))
 }
 
 #[cfg(test)]
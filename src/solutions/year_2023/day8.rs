@@ -66,13 +66,13 @@ fn solve_part2(input: &str) -> u64 {
 
 fn lcm(mut iter: impl Iterator<Item = u64>) -> u64 {
     let init = iter.next().unwrap();
-    iter.fold(init, |acc, num| num::integer::lcm(acc, num))
+    iter.fold(init, num::integer::lcm)
 }
 
-pub fn solve(input: crate::Input) {
-    let input = input.read_all();
-    let answer1 = solve_part1(&input);
-    println!("Answer #1: {answer1}");
-    let answer2 = solve_part2(&input);
-    println!("Answer #2: {answer2}");
+pub fn part1(input: crate::Input) -> u64 {
+    solve_part1(&input.read_all())
+}
+
+pub fn part2(input: crate::Input) -> u64 {
+    solve_part2(&input.read_all())
 }
@@ -1,3 +1,3 @@
 use crate::solutions::macros::days;
 
-days!(2024, day1, day2, day3, day4, day5, day6, day7, day8, day9, day11, day13, day14, day15);
+days!(2024, day1: parse, day2, day3, day4, day5, day6, day7, day8, day9, day10, day11, day12: solve, day13, day14, day15);
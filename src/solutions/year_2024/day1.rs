@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
-pub fn part1(input: crate::Input) -> i32 {
-    let (mut l, mut r) = parse(input);
+pub fn part1((l, r): &(Vec<u32>, Vec<u32>)) -> i32 {
+    let (mut l, mut r) = (l.clone(), r.clone());
     l.sort();
     r.sort();
 
@@ -13,12 +13,11 @@ pub fn part1(input: crate::Input) -> i32 {
     d
 }
 
-pub fn part2(input: crate::Input) -> u32 {
-    let (l, r) = parse(input);
-    let mut counts: HashMap<_, _> = l.into_iter().map(|n| (n, 0)).collect();
+pub fn part2((l, r): &(Vec<u32>, Vec<u32>)) -> u32 {
+    let mut counts: HashMap<_, _> = l.iter().map(|&n| (n, 0)).collect();
 
     for n in r {
-        if let Some(count) = counts.get_mut(&n) {
+        if let Some(count) = counts.get_mut(n) {
             *count += 1;
         }
     }
@@ -31,7 +30,7 @@ pub fn part2(input: crate::Input) -> u32 {
     s
 }
 
-fn parse(input: crate::Input) -> (Vec<u32>, Vec<u32>) {
+pub fn parse(input: crate::Input) -> (Vec<u32>, Vec<u32>) {
     let mut l = Vec::new();
     let mut r = Vec::new();
 
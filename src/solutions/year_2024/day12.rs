@@ -2,13 +2,13 @@ use std::collections::{HashSet, VecDeque};
 
 use crate::util::grid::{Grid, GridCoords};
 
-fn solve(input: crate::Input) -> (usize, usize) {
+pub fn solve(input: crate::Input) -> (usize, usize) {
     let grid = Grid::<char>::parse(input);
     let mut total_p1 = 0;
     let mut total_p2 = 0;
     let mut seen = HashSet::new();
-    for (row, values) in grid.data().into_iter().enumerate() {
-        for (col, &value) in values.into_iter().enumerate() {
+    for (row, values) in grid.data().iter().enumerate() {
+        for (col, &value) in values.iter().enumerate() {
             let coords = GridCoords { row, col };
             if seen.contains(&coords) {
                 continue;
@@ -52,7 +52,7 @@ fn solve(input: crate::Input) -> (usize, usize) {
                     let in_region = positions.contains(&GridCoords { row, col });
                     let empty_right = !positions.contains(&GridCoords { row, col: col + 1 });
                     let empty_left =
-                        (col > 0).then(|| !positions.contains(&GridCoords { row, col: col - 1 })).unwrap_or(true);
+                        if col > 0 { !positions.contains(&GridCoords { row, col: col - 1 }) } else { true };
                     let next_on_edge_left = in_region && empty_left;
                     let next_on_edge_right = in_region && empty_right;
                     if !next_on_edge_left && on_edge_left {
@@ -80,14 +80,6 @@ fn solve(input: crate::Input) -> (usize, usize) {
     (total_p1, total_p2)
 }
 
-pub fn part1(input: crate::Input) -> usize {
-    solve(input).0
-}
-
-pub fn part2(input: crate::Input) -> usize {
-    solve(input).1
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -102,8 +94,7 @@ EEEC
 "#
         .trim();
 
-        assert_eq!(part1(crate::Input::memory(input)), 140);
-        assert_eq!(part2(crate::Input::memory(input)), 80);
+        assert_eq!(solve(crate::Input::memory(input)), (140, 80));
     }
 
     #[test]
@@ -117,8 +108,7 @@ OOOOO
 "#
         .trim();
 
-        assert_eq!(part1(crate::Input::memory(input)), 772);
-        assert_eq!(part2(crate::Input::memory(input)), 436);
+        assert_eq!(solve(crate::Input::memory(input)), (772, 436));
     }
 
     #[test]
@@ -137,7 +127,6 @@ OOOOO
             "#
         .trim();
 
-        assert_eq!(part1(crate::Input::memory(input)), 1930);
-        assert_eq!(part2(crate::Input::memory(input)), 1206);
+        assert_eq!(solve(crate::Input::memory(input)), (1930, 1206));
     }
 }
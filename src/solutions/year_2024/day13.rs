@@ -25,7 +25,7 @@ impl Config {
 }
 
 fn parse(input: crate::Input) -> Vec<Config> {
-    input.read_all().split("\n\n").map(|chunk| Config::parse(chunk)).collect()
+    input.read_all().split("\n\n").map(Config::parse).collect()
 }
 
 fn min_tokens(config: Config) -> Option<u64> {
@@ -82,7 +82,7 @@ mod test {
     #[test]
     fn min_tokens_example() {
         let config = Config::parse(
-            &r#"
+            r#"
 Button A: X+94, Y+34
 Button B: X+22, Y+67
 Prize: X=8400, Y=5400
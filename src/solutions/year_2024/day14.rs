@@ -63,7 +63,7 @@ pub fn part1(input: crate::Input) -> u32 {
     counts.into_iter().product()
 }
 
-pub fn part2(input: crate::Input) -> u32 {
+pub fn part2(input: crate::Input) -> crate::Answer {
     const IN_ROW: u32 = 16;
 
     let mut robots = parse(input);
@@ -72,7 +72,7 @@ pub fn part2(input: crate::Input) -> u32 {
         robots.iter_mut().for_each(|robot| {
             robot.tick(1);
         });
-        let robot_positions: HashSet<_> = HashSet::from_iter(robots.iter().map(|robot| robot.position.clone()));
+        let robot_positions: HashSet<_> = HashSet::from_iter(robots.iter().map(|robot| robot.position));
         for y in 0..HEIGHT {
             for x in 0..WIDTH - IN_ROW {
                 if (x..=x + IN_ROW).all(|x| robot_positions.contains(&Vec2::new(x, y))) {
@@ -83,19 +83,16 @@ pub fn part2(input: crate::Input) -> u32 {
         tick += 1;
     }
     let positions: HashSet<_> = robots.into_iter().map(|robot| robot.position).collect();
-    debug_grid(&positions);
-    tick
+    crate::Answer::rendered(tick, render(&positions))
 }
 
-fn debug_grid(positions: &HashSet<Vec2<u32>>) {
+fn render(positions: &HashSet<Vec2<u32>>) -> String {
+    let mut rendered = String::new();
     for y in 0..HEIGHT {
         for x in 0..WIDTH {
-            if positions.contains(&Vec2::new(x, y)) {
-                print!("R");
-            } else {
-                print!(".");
-            }
+            rendered.push(if positions.contains(&Vec2::new(x, y)) { 'R' } else { '.' });
         }
-        println!();
+        rendered.push('\n');
     }
+    rendered
 }
@@ -1,8 +1,7 @@
-use crate::util::grid::Grid;
 use crate::util::math::{Point2, Vec2};
 
 struct Input {
-    grid: Grid<char>,
+    grid: Vec<Vec<char>>,
     moves: Vec<char>,
     robot_pos: Point2<i32>,
 }
@@ -29,10 +28,10 @@ fn parse(input: crate::Input) -> Input {
     Input { grid, moves, robot_pos: robot_pos.unwrap() }
 }
 
-fn score(grid: &Grid<char>, box_repr: char) -> usize {
-    grid.into_iter()
+fn score(grid: &[Vec<char>], box_repr: char) -> usize {
+    grid.iter()
         .enumerate()
-        .flat_map(|(y, row)| row.into_iter().enumerate().map(move |(x, col)| (x, y, col)))
+        .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, col)| (x, y, col)))
         .filter(|(_, _, value)| **value == box_repr)
         .fold(0, |total, (x, y, _)| total + (100 * y + x))
 }
@@ -57,7 +56,7 @@ pub fn part1(input: crate::Input) -> usize {
     score(&grid, 'O')
 }
 
-fn move_robot1(grid: &mut Grid<char>, pos: Point2<i32>, move_: char) -> bool {
+fn move_robot1(grid: &mut Vec<Vec<char>>, pos: Point2<i32>, move_: char) -> bool {
     let object = grid[pos.y as usize][pos.x as usize];
     match object {
         '#' => return false,
@@ -77,7 +76,7 @@ fn move_robot1(grid: &mut Grid<char>, pos: Point2<i32>, move_: char) -> bool {
 
 pub fn part2(input: crate::Input) -> usize {
     let Input { grid, moves, mut robot_pos } = parse(input);
-    let mut double_grid: Grid<_> = (0..grid.len())
+    let mut double_grid: Vec<Vec<_>> = (0..grid.len())
         .map(|row| {
             (0..grid[0].len())
                 .flat_map(|col| match grid[row][col] {
@@ -101,7 +100,7 @@ pub fn part2(input: crate::Input) -> usize {
     score(&double_grid, '[')
 }
 
-fn move_robot2(grid: &mut Grid<char>, pos: Point2<i32>, move_: char) -> bool {
+fn move_robot2(grid: &mut Vec<Vec<char>>, pos: Point2<i32>, move_: char) -> bool {
     let is_vertical = matches!(move_, '^' | 'v');
     let additional_offset = match grid[pos.y as usize][pos.x as usize] {
         '#' => return false,
@@ -20,7 +20,7 @@ pub fn part1(input: crate::Input) -> usize {
                 Direction::BackwardDiagonal,
             ] {
                 if let Some(word) = get_four_letter_word(&wordsearch, r, c, direction) {
-                    if word == WORD || &word.chars().rev().collect::<String>() == WORD {
+                    if word == WORD || word.chars().rev().collect::<String>() == WORD {
                         sum += 1;
                     }
                 }
@@ -39,7 +39,7 @@ enum Direction {
 }
 
 fn get_four_letter_word(
-    wordsearch: &Vec<Vec<char>>,
+    wordsearch: &[Vec<char>],
     r: usize,
     c: usize,
     direction: Direction,
@@ -87,7 +87,7 @@ pub fn part2(input: crate::Input) -> usize {
     sum
 }
 
-fn window_contains_x_mas(wordsearch: &Vec<Vec<char>>, root_row: usize, root_col: usize) -> bool {
+fn window_contains_x_mas(wordsearch: &[Vec<char>], root_row: usize, root_col: usize) -> bool {
     const WORD: &str = "MAS";
 
     let mut fw_diagonal_word = String::new();
@@ -101,6 +101,6 @@ fn window_contains_x_mas(wordsearch: &Vec<Vec<char>>, root_row: usize, root_col:
         bw_diagonal_word.push(wordsearch[row][bw_diagonal_col]);
     }
 
-    (fw_diagonal_word == WORD || &fw_diagonal_word.chars().rev().collect::<String>() == WORD)
-        && (bw_diagonal_word == WORD || &bw_diagonal_word.chars().rev().collect::<String>() == WORD)
+    (fw_diagonal_word == WORD || fw_diagonal_word.chars().rev().collect::<String>() == WORD)
+        && (bw_diagonal_word == WORD || bw_diagonal_word.chars().rev().collect::<String>() == WORD)
 }
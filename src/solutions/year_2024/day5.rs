@@ -43,7 +43,7 @@ pub fn part1(input: crate::Input) -> u32 {
 
 fn check(update: &Update, pair: Pair) -> bool {
     let mut idxs: (Option<usize>, Option<usize>) = (None, None);
-    for (idx, &page) in update.into_iter().enumerate() {
+    for (idx, &page) in update.iter().enumerate() {
         if page == pair.0 {
             idxs.0 = Some(idx);
         } else if page == pair.1 {
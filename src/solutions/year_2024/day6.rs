@@ -90,8 +90,6 @@ pub fn part2(input: crate::Input) -> usize {
     path.par_iter().for_each({
         let sum = sum.clone();
         move |&(row, col)| {
-            let row = row as i32;
-            let col = col as i32;
             let mut obstacles = input.obstacles.clone();
             if (row, col) == input.guard || !obstacles.insert((row, col)) {
                 return;
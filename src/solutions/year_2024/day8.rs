@@ -18,7 +18,7 @@ impl Input {
         let mut nodes = HashMap::new();
         for (i, chars) in grid.into_iter().enumerate() {
             for (j, char_) in chars.into_iter().enumerate().filter(|(_, char_)| *char_ != '.') {
-                nodes.entry(char_).or_insert_with(|| Vec::new()).push((i as u32, j as u32));
+                nodes.entry(char_).or_insert_with(Vec::new).push((i as u32, j as u32));
             }
         }
         Self { nodes, grid_height, grid_width }
@@ -28,7 +28,7 @@ impl Input {
 pub fn part1(input: crate::Input) -> usize {
     let input = Input::parse(input);
     let mut antinode_spots = HashSet::new();
-    for (_, positions) in &input.nodes {
+    for positions in input.nodes.values() {
         for (idx, &pos1) in positions.iter().enumerate() {
             for &pos2 in positions.iter().skip(idx + 1) {
                 let (a1, a2) = antinodes(pos1, pos2);
@@ -62,7 +62,7 @@ fn dx_dy(node1: SafePos, node2: SafePos) -> (i32, i32) {
 pub fn part2(input: crate::Input) -> usize {
     let input = Input::parse(input);
     let mut antinode_spots = HashSet::new();
-    for (_, positions) in &input.nodes {
+    for positions in input.nodes.values() {
         for (idx, &pos1) in positions.iter().enumerate() {
             for &pos2 in positions.iter().skip(idx + 1) {
                 antinode_spots.insert(pos1);
@@ -86,51 +86,4 @@ pub fn part2(input: crate::Input) -> usize {
     antinode_spots.len()
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn part1_example() {
-        let input = crate::Input::memory(
-            r#"
-............
-........0...
-.....0......
-.......0....
-....0.......
-......A.....
-............
-............
-........A...
-.........A..
-............
-............
-"#
-            .trim(),
-        );
-        assert_eq!(part1(input), 14);
-    }
-
-    #[test]
-    fn part2_example() {
-        let input = crate::Input::memory(
-            r#"
-............
-........0...
-.....0......
-.......0....
-....0.......
-......A.....
-............
-............
-........A...
-.........A..
-............
-............
-"#
-            .trim(),
-        );
-        assert_eq!(part2(input), 34);
-    }
-}
+crate::solutions::macros::examples!(2024, 8, part1, part2);
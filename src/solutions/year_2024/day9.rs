@@ -57,10 +57,10 @@ fn parse(input: crate::Input) -> Vec<RefCell<Block>> {
         .collect()
 }
 
-fn checksum(blocks: &Vec<RefCell<Block>>) -> u64 {
+fn checksum(blocks: &[RefCell<Block>]) -> u64 {
     let mut position: usize = 0;
     let mut checksum = 0;
-    for block in blocks.into_iter() {
+    for block in blocks.iter() {
         let block = block.borrow();
         for &file_id in &block.data {
             checksum += position as u64 * file_id as u64;
@@ -0,0 +1,139 @@
+use std::fmt;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use lazy_static::lazy_static;
+use regex::Regex;
+use url::Url;
+
+use crate::account::Account;
+use crate::description::strip_tags;
+use crate::input::{base_url, build_page_url, client};
+
+lazy_static! {
+    /// Matches a row of the personal stats table: the day, then time, rank and score for part 1
+    /// and then part 2 (which are `-` if part 2 has not been solved).
+    static ref ROW_REGEX: Regex =
+        Regex::new(r"(?m)^\s*(\d+)\s+(\S+)\s+(\S+)\s+(\S+)\s+(\S+)\s+(\S+)\s+(\S+)\s*$").unwrap();
+}
+
+/// How long it took to solve a part, measured from when the challenge unlocked.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SolveTime {
+    Within(Duration),
+    /// AoC does not say how long it took beyond the first 24 hours.
+    OverADay,
+}
+
+impl fmt::Display for SolveTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Within(time) => {
+                let secs = time.as_secs();
+                write!(f, "{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+            },
+            Self::OverADay => write!(f, ">24h"),
+        }
+    }
+}
+
+/// The personal leaderboard stats for one part of a day's challenge.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct PartStats {
+    pub time: SolveTime,
+    pub rank: u32,
+    pub score: u32,
+}
+
+/// The personal leaderboard stats for a day's challenge.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct DayStats {
+    pub day: u8,
+    pub part1: PartStats,
+    /// `None` if only part 1 has been solved.
+    pub part2: Option<PartStats>,
+}
+
+/// Fetch the account's personal leaderboard stats for the year, ordered by day.
+pub fn fetch(year: u16, account: &Account) -> anyhow::Result<Vec<DayStats>> {
+    fetch_from(&base_url()?, &account.session_token()?, year)
+}
+
+pub(crate) fn fetch_from(base: &Url, session: &str, year: u16) -> anyhow::Result<Vec<DayStats>> {
+    let url = build_page_url(base, &[&year.to_string(), "leaderboard", "self"]);
+    let response =
+        client().get(url).header("Cookie", format!("session={session}")).send().context("failed to fetch stats")?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(anyhow!("non-200 status returned when fetching stats: {status}"));
+    }
+    let html = response.text().context("failed to read stats")?;
+    if !html.contains("personal leaderboard statistics") && !html.contains("You haven't collected any stars") {
+        return Err(anyhow!("unrecognised stats page, the session may have expired"));
+    }
+    Ok(parse(&html))
+}
+
+/// Parse the table on the personal stats page, ordered by day.
+pub fn parse(html: &str) -> Vec<DayStats> {
+    let text = strip_tags(html);
+    let mut days: Vec<_> = ROW_REGEX
+        .captures_iter(&text)
+        .filter_map(|row| {
+            let day = row[1].parse().ok()?;
+            let part1 = parse_part(&row[2], &row[3], &row[4])?;
+            let part2 = parse_part(&row[5], &row[6], &row[7]);
+            Some(DayStats { day, part1, part2 })
+        })
+        .collect();
+    days.sort_by_key(|stats| stats.day);
+    days
+}
+
+fn parse_part(time: &str, rank: &str, score: &str) -> Option<PartStats> {
+    let time = match time {
+        ">24h" => SolveTime::OverADay,
+        time => {
+            let mut fields = time.split(':').map(str::parse::<u64>);
+            let (hours, minutes, seconds) = (fields.next()?.ok()?, fields.next()?.ok()?, fields.next()?.ok()?);
+            SolveTime::Within(Duration::from_secs(hours * 3600 + minutes * 60 + seconds))
+        },
+    };
+    Some(PartStats { time, rank: rank.parse().ok()?, score: score.parse().ok()? })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::MockServer;
+
+    const FIXTURE: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/stats/2024.html"));
+
+    #[test]
+    fn parses_stats_table() {
+        let days = parse(FIXTURE);
+        assert_eq!(days.iter().map(|stats| stats.day).collect::<Vec<_>>(), [1, 2, 12, 15]);
+        assert_eq!(days[0], DayStats {
+            day: 1,
+            part1: PartStats { time: SolveTime::Within(Duration::from_secs(151)), rank: 42, score: 59 },
+            part2: Some(PartStats { time: SolveTime::Within(Duration::from_secs(240)), rank: 101, score: 0 }),
+        });
+        assert_eq!(days[2].part2.unwrap().time, SolveTime::OverADay);
+        assert_eq!(days[3].part1.time.to_string(), "01:02:03");
+        assert_eq!(days[3].part2, None);
+    }
+
+    #[test]
+    fn fetches_stats_page() {
+        let server = MockServer::start();
+        server.fixture("GET", "/2024/leaderboard/self", "stats/2024.html");
+        assert_eq!(fetch_from(server.url(), "abc", 2024).unwrap(), parse(FIXTURE));
+        assert_eq!(server.requests()[0].header("cookie"), Some("session=abc"));
+        assert!(fetch_from(server.url(), "abc", 2023).is_err());
+    }
+
+    #[test]
+    fn ignores_page_without_stars() {
+        assert!(parse("<article><p>You haven't collected any stars... yet.</p></article>").is_empty());
+    }
+}
@@ -0,0 +1,158 @@
+use std::fmt;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use lazy_static::lazy_static;
+use regex::Regex;
+use url::Url;
+
+use crate::account::Account;
+use crate::date::ChallengeDate;
+use crate::input::{base_url, build_day_url, client};
+use crate::solutions::Part;
+use crate::Answer;
+
+lazy_static! {
+    /// Matches the remaining cooldown in a rate limited response, e.g. "You have 1m 5s left to wait".
+    static ref WAIT_REGEX: Regex = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+}
+
+/// How AoC responded to a submitted answer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// The answer was wrong and AoC did not say in which direction.
+    Wrong,
+    /// An answer was submitted too recently, the wait is how long until the next one is accepted.
+    RateLimited(Duration),
+    /// This part has already been solved, so the answer was not checked.
+    AlreadySolved,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::TooHigh => write!(f, "wrong, too high"),
+            Self::TooLow => write!(f, "wrong, too low"),
+            Self::Wrong => write!(f, "wrong"),
+            Self::RateLimited(wait) => write!(f, "rate limited, {}s left to wait", wait.as_secs()),
+            Self::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// Submit the answer for one part of the given day's challenge to AoC's website, as the given
+/// account.
+pub fn submit(date: ChallengeDate, part: Part, answer: &Answer, account: &Account) -> anyhow::Result<Verdict> {
+    submit_to(&base_url()?, &account.session_token()?, date, part, answer)
+}
+
+fn submit_to(base: &Url, session: &str, date: ChallengeDate, part: Part, answer: &Answer) -> anyhow::Result<Verdict> {
+    let url = build_day_url(base, &date, "answer");
+    let response = client()
+        .post(url)
+        .header("Cookie", format!("session={session}"))
+        .form(&[("level", part.to_string()), ("answer", answer.value().to_string())])
+        .send()
+        .context("failed to submit answer")?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(anyhow!("non-200 status returned when submitting answer: {status}"));
+    }
+
+    let body = response.text().context("failed to read answer response")?;
+    classify(&body).ok_or_else(|| anyhow!("unrecognised response when submitting answer:\n{body}"))
+}
+
+/// Determine the verdict from the HTML of the answer response page.
+fn classify(html: &str) -> Option<Verdict> {
+    if html.contains("That's the right answer") {
+        return Some(Verdict::Correct);
+    }
+    if html.contains("Did you already complete it?") {
+        return Some(Verdict::AlreadySolved);
+    }
+    if html.contains("You gave an answer too recently") {
+        let captures = WAIT_REGEX.captures(html)?;
+        let minutes: u64 = captures.get(1).map_or(Ok(0), |m| m.as_str().parse()).ok()?;
+        let seconds: u64 = captures[2].parse().ok()?;
+        return Some(Verdict::RateLimited(Duration::from_secs(minutes * 60 + seconds)));
+    }
+    if html.contains("That's not the right answer") {
+        if html.contains("your answer is too high") {
+            return Some(Verdict::TooHigh);
+        }
+        if html.contains("your answer is too low") {
+            return Some(Verdict::TooLow);
+        }
+        return Some(Verdict::Wrong);
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock::MockServer;
+
+    fn submit_with_response(body: &'static str) -> (Verdict, crate::mock::Request) {
+        let server = MockServer::start();
+        server.route("POST", "/2024/day/7/answer", 200, body);
+        let date = ChallengeDate { year: 2024, day: 7 };
+        let verdict = submit_to(server.url(), "abc", date, Part::Two, &Answer::from(42_u32)).unwrap();
+        (verdict, server.requests().pop().unwrap())
+    }
+
+    #[test]
+    fn posts_answer_form() {
+        let (verdict, request) = submit_with_response(
+            "<article><p>That's the right answer!  You are <em>one gold star</em> closer.</p></article>",
+        );
+        assert_eq!(verdict, Verdict::Correct);
+        assert_eq!((request.method.as_str(), request.path.as_str()), ("POST", "/2024/day/7/answer"));
+        assert_eq!(request.header("cookie"), Some("session=abc"));
+        assert!(request.header("user-agent").unwrap().starts_with("github.com/mathew-horner/advent-of-code"));
+        assert_eq!(request.body, "level=2&answer=42");
+    }
+
+    #[test]
+    fn reports_rate_limit() {
+        let (verdict, _) = submit_with_response(
+            "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying \
+             again.  You have 37s left to wait. <a href=\"/2024/day/7\">[Return to Day 7]</a></p></article>",
+        );
+        assert_eq!(verdict, Verdict::RateLimited(Duration::from_secs(37)));
+    }
+
+    #[test]
+    fn classifies_responses() {
+        let (verdict, _) = submit_with_response(
+            "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're \
+             using the full input data.</p></article>",
+        );
+        assert_eq!(verdict, Verdict::TooHigh);
+        assert_eq!(classify("That's not the right answer; your answer is too low."), Some(Verdict::TooLow));
+        assert_eq!(classify("That's not the right answer.  If you're stuck..."), Some(Verdict::Wrong));
+        assert_eq!(
+            classify("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait."),
+            Some(Verdict::RateLimited(Duration::from_secs(65)))
+        );
+        assert_eq!(
+            classify("You don't seem to be solving the right level.  Did you already complete it?"),
+            Some(Verdict::AlreadySolved)
+        );
+        assert_eq!(classify("<html>Puzzle inputs differ by user.</html>"), None);
+    }
+
+    #[test]
+    fn fails_on_server_error() {
+        let server = MockServer::start();
+        server.route("POST", "/2024/day/7/answer", 500, "oops");
+        let date = ChallengeDate { year: 2024, day: 7 };
+        assert!(submit_to(server.url(), "abc", date, Part::One, &Answer::from(1_u32)).is_err());
+    }
+}
@@ -1,12 +1,200 @@
-pub type Grid<T> = Vec<Vec<T>>;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+/// Offsets to the orthogonally adjacent cells (N, E, S, W).
+#[rustfmt::skip]
+const ADJACENT4: [GridCoords<isize>; 4] = [
+    GridCoords { row: -1, col: 0 },
+    GridCoords { row: 0, col: 1 },
+    GridCoords { row: 1, col: 0 },
+    GridCoords { row: 0, col: -1 },
+];
+
+/// Offsets to the orthogonally and diagonally adjacent cells, clockwise from N.
+#[rustfmt::skip]
+const ADJACENT8: [GridCoords<isize>; 8] = [
+    GridCoords { row: -1, col: 0 },
+    GridCoords { row: -1, col: 1 },
+    GridCoords { row: 0, col: 1 },
+    GridCoords { row: 1, col: 1 },
+    GridCoords { row: 1, col: 0 },
+    GridCoords { row: 1, col: -1 },
+    GridCoords { row: 0, col: -1 },
+    GridCoords { row: -1, col: -1 },
+];
+
+/// A position within a `Grid`.
+///
+/// Signed coordinates (`GridCoords<isize>`) are used for offsets and for positions that may fall
+/// outside of the grid.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct GridCoords<T = usize> {
+    pub row: T,
+    pub col: T,
+}
+
+impl GridCoords {
+    /// Apply a signed offset to these coordinates. The result may be out of bounds.
+    pub fn with_offset(self, offset: GridCoords<isize>) -> GridCoords<isize> {
+        GridCoords { row: self.row as isize + offset.row, col: self.col as isize + offset.col }
+    }
+}
+
+/// The position and value of a single cell.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct CellData<T> {
+    pub position: GridCoords,
+    pub value: T,
+}
+
+impl<T: Clone> CellData<&T> {
+    /// Clone the borrowed value so the data can outlive the grid.
+    pub fn to_owned(self) -> CellData<T> {
+        CellData { position: self.position, value: self.value.clone() }
+    }
+}
+
+/// A handle to a cell that borrows the grid it belongs to, so that neighbouring cells can be
+/// looked up from it.
+#[derive(Copy, Clone, Debug)]
+pub struct Cell<'a, T> {
+    pub grid: &'a Grid<T>,
+    pub data: CellData<&'a T>,
+}
+
+impl<'a, T> Cell<'a, T> {
+    /// Iterate over the in-bounds orthogonally adjacent cells.
+    pub fn adjacent4(&self) -> impl Iterator<Item = Cell<'a, T>> + 'a {
+        self.adjacent(&ADJACENT4)
+    }
+
+    /// Iterate over the in-bounds orthogonally and diagonally adjacent cells.
+    pub fn adjacent8(&self) -> impl Iterator<Item = Cell<'a, T>> + 'a {
+        self.adjacent(&ADJACENT8)
+    }
+
+    fn adjacent(&self, offsets: &'static [GridCoords<isize>]) -> impl Iterator<Item = Cell<'a, T>> + 'a {
+        let grid = self.grid;
+        let position = self.data.position;
+        offsets.iter().filter_map(move |&offset| grid.get_with_signed_coords(position.with_offset(offset)))
+    }
+}
+
+/// A rectangular, row-major 2D grid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grid<T> {
+    data: Vec<Vec<T>>,
+}
+
+impl<T> Grid<T> {
+    /// Create a grid from rows of values.
+    ///
+    /// Panics if the rows are not all the same length.
+    pub fn new(data: Vec<Vec<T>>) -> Self {
+        if let Some(first) = data.first() {
+            assert!(data.iter().all(|row| row.len() == first.len()), "grid rows must all be the same length");
+        }
+        Self { data }
+    }
+
+    /// Returns the underlying rows.
+    pub fn data(&self) -> &[Vec<T>] {
+        &self.data
+    }
+
+    /// Get the cell at the given coordinates, if they are in bounds.
+    pub fn get(&self, position: GridCoords) -> Option<Cell<'_, T>> {
+        let value = self.data.get(position.row)?.get(position.col)?;
+        Some(Cell { grid: self, data: CellData { position, value } })
+    }
+
+    /// Get the cell at the given signed coordinates, if they are in bounds.
+    pub fn get_with_signed_coords(&self, position: GridCoords<isize>) -> Option<Cell<'_, T>> {
+        let row = usize::try_from(position.row).ok()?;
+        let col = usize::try_from(position.col).ok()?;
+        self.get(GridCoords { row, col })
+    }
+
+    /// Iterate over every cell, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = Cell<'_, T>> {
+        self.data.iter().enumerate().flat_map(move |(row, values)| {
+            values.iter().enumerate().map(move |(col, value)| Cell {
+                grid: self,
+                data: CellData { position: GridCoords { row, col }, value },
+            })
+        })
+    }
+
+    /// Iterate over every cell whose value matches the predicate.
+    pub fn find_all<'a>(&'a self, predicate: impl Fn(&T) -> bool + 'a) -> impl Iterator<Item = Cell<'a, T>> {
+        self.cells().filter(move |cell| predicate(cell.data.value))
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    /// Parse a grid where every character is parsed into a single cell value.
+    ///
+    /// Leading and trailing whitespace on each line is ignored.
+    pub fn parse(input: crate::Input) -> Self {
+        let data = input
+            .read_lines()
+            .map(|line| line.trim().chars().map(|ch| ch.encode_utf8(&mut [0; 4]).parse().unwrap()).collect())
+            .collect();
+        Self::new(data)
+    }
+}
 
 #[allow(dead_code)]
 pub fn debug_grid<T>(grid: &Grid<T>)
 where
     T: std::fmt::Display,
 {
-    grid.into_iter().for_each(|row| {
-        row.into_iter().for_each(|col| print!("{col}"));
+    grid.data().iter().for_each(|row| {
+        row.iter().for_each(|col| print!("{col}"));
         println!();
     });
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn grid() -> Grid<u32> {
+        Grid::parse(crate::Input::memory("123\n456\n789"))
+    }
+
+    #[test]
+    fn adjacent4_in_bounds() {
+        let grid = grid();
+        let corner: Vec<_> =
+            grid.get(GridCoords { row: 0, col: 0 }).unwrap().adjacent4().map(|c| *c.data.value).collect();
+        assert_eq!(corner, vec![2, 4]);
+        let center: Vec<_> =
+            grid.get(GridCoords { row: 1, col: 1 }).unwrap().adjacent4().map(|c| *c.data.value).collect();
+        assert_eq!(center, vec![2, 6, 8, 4]);
+    }
+
+    #[test]
+    fn adjacent8_in_bounds() {
+        let grid = grid();
+        let corner: Vec<_> =
+            grid.get(GridCoords { row: 2, col: 2 }).unwrap().adjacent8().map(|c| *c.data.value).collect();
+        assert_eq!(corner, vec![6, 8, 5]);
+        assert_eq!(grid.get(GridCoords { row: 1, col: 1 }).unwrap().adjacent8().count(), 8);
+    }
+
+    #[test]
+    fn signed_lookup() {
+        let grid = grid();
+        assert!(grid.get_with_signed_coords(GridCoords { row: -1, col: 0 }).is_none());
+        assert!(grid.get_with_signed_coords(GridCoords { row: 0, col: 3 }).is_none());
+        let origin = GridCoords { row: 2, col: 0 };
+        let cell = grid.get_with_signed_coords(origin.with_offset(GridCoords { row: -1, col: 2 })).unwrap();
+        assert_eq!(*cell.data.value, 6);
+        assert_eq!(grid.find_all(|&v| v % 2 == 0).count(), 4);
+    }
+}